@@ -0,0 +1,113 @@
+﻿using Microsoft.VisualStudio.TestTools.UnitTesting;
+using System;
+using System.IO;
+using System.Text.Json;
+using System.Collections.Generic;
+using HMACSerialiser;
+using static HMACSerialiser.HMAC.HMACHelper;
+
+namespace HMACTests
+{
+    /// <summary>
+    /// Test vectors shared with the Rust crate, generated by test-vectors/generate_hmac_serialiser.py
+    /// </summary>
+    [TestClass]
+    public class InteropVectorTests
+    {
+        private const string vectorsFile = "hmac_serialiser.json";
+
+        private static IEnumerable<JsonElement> LoadVectors()
+        {
+            string path = Path.Combine(AppContext.BaseDirectory, vectorsFile);
+            using (JsonDocument doc = JsonDocument.Parse(File.ReadAllText(path)))
+            {
+                foreach (JsonElement vector in doc.RootElement.GetProperty("vectors").EnumerateArray())
+                    yield return vector.Clone();
+            }
+        }
+
+        private static string GetStringOrNull(JsonElement vector, string property)
+        {
+            JsonElement value = vector.GetProperty(property);
+            return value.ValueKind == JsonValueKind.Null ? null : value.GetString();
+        }
+
+        private static ISerialiser InitialiseSerialiser(JsonElement vector)
+        {
+            string key = vector.GetProperty("key").GetString();
+            string salt = GetStringOrNull(vector, "salt");
+            string info = GetStringOrNull(vector, "info");
+            string sep = vector.GetProperty("sep").GetString();
+            var hashFunction = Enum.Parse<HMACHashAlgorithm>(vector.GetProperty("algorithm").GetString());
+
+            switch (vector.GetProperty("serialiser").GetString())
+            {
+                case "serialiser":
+                    return new Serialiser(key, salt, hashFunction, info, sep);
+                case "urlsafe-serialiser":
+                    return new URLSafeSerialiser(key, salt, hashFunction, info, sep);
+                case "timed-serialiser":
+                    return new TimedSerialiser(key, salt, vector.GetProperty("max_age").GetInt64(), hashFunction, info, sep);
+                case "timed-urlsafe-serialiser":
+                    return new TimedURLSafeSerialiser(key, salt, vector.GetProperty("max_age").GetInt64(), hashFunction, info, sep);
+                default:
+                    throw new ArgumentOutOfRangeException();
+            }
+        }
+
+        private static object GetPayload(JsonElement vector)
+        {
+            JsonElement payload = vector.GetProperty("payload");
+            if (payload.ValueKind == JsonValueKind.String)
+                return payload.GetString();
+            return payload;
+        }
+
+        [TestMethod]
+        public void DumpsMatchesVectors()
+        {
+            foreach (JsonElement vector in LoadVectors())
+            {
+                ISerialiser serialiser = InitialiseSerialiser(vector);
+                object payload = GetPayload(vector);
+
+                string signed;
+                if (serialiser is ITimedSerialiser timedSerialiser)
+                {
+                    var dateTime = DateTimeOffset.FromUnixTimeSeconds(vector.GetProperty("timestamp").GetInt64());
+                    signed = timedSerialiser.Dumps(payload, dateTime);
+                }
+                else
+                {
+                    signed = serialiser.Dumps(payload);
+                }
+
+                Assert.AreEqual(vector.GetProperty("token").GetString(), signed, vector.GetProperty("name").GetString());
+            }
+        }
+
+        [TestMethod]
+        public void LoadsMatchesVectors()
+        {
+            foreach (JsonElement vector in LoadVectors())
+            {
+                ISerialiser serialiser = InitialiseSerialiser(vector);
+                string token = vector.GetProperty("token").GetString();
+                string name = vector.GetProperty("name").GetString();
+
+                JsonElement payload = vector.GetProperty("payload");
+                if (payload.ValueKind == JsonValueKind.String)
+                {
+                    Assert.AreEqual(payload.GetString(), serialiser.LoadsString(token), name);
+                    continue;
+                }
+
+                JSONPayload result = serialiser.Loads(token);
+                Assert.AreEqual(
+                    JsonSerializer.Serialize(payload),
+                    JsonSerializer.Serialize(result.jsonDoc.RootElement),
+                    name);
+            }
+        }
+    }
+}
@@ -0,0 +1,152 @@
+//! Actix Web integration, enabled via the `actix` feature.
+//!
+//! The [`Signed`] extractor verifies the token of a request with the [`TokenVerifier`] in the app data,
+//! while [`require_token`] can be used with `actix_web::middleware::from_fn` to reject requests
+//! with invalid tokens before the handlers run. Errors are turned into JSON responses via [`Error::status_code`].
+//!
+//! The verifier can be registered either as `app_data(verifier)` or as `app_data(web::Data::new(verifier))`.
+//!
+//! Sample Usage:
+//! ```rust
+//! use actix_web::{web, App};
+//! use hmac_serialiser::actix::Signed;
+//! use hmac_serialiser::web::{TokenLocation, TokenVerifier};
+//! use hmac_serialiser::{Algorithm, Encoder, HmacSigner, KeyInfo, Payload};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, Debug)]
+//! struct UserData {
+//!     username: String,
+//! }
+//! impl Payload for UserData {
+//!     fn get_exp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+//!         None
+//!     }
+//! }
+//!
+//! async fn profile(Signed(user): Signed<UserData>) -> String {
+//!     user.username
+//! }
+//!
+//! let key_info = KeyInfo {
+//!     key: b"your_secret_key".to_vec(),
+//!     salt: vec![],
+//!     info: vec![],
+//! };
+//! let signer = HmacSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+//! let verifier = TokenVerifier::new(signer).with_location(TokenLocation::Cookie("session".to_string()));
+//!
+//! let app = App::new()
+//!     .app_data(web::Data::new(verifier))
+//!     .route("/profile", web::get().to(profile));
+//! ```
+use crate::web::TokenVerifier;
+use crate::{Error, Payload};
+use actix_web::body::MessageBody;
+use actix_web::dev::{Payload as RequestPayload, ServiceRequest, ServiceResponse};
+use actix_web::http::{header, StatusCode};
+use actix_web::middleware::Next;
+use actix_web::{web, FromRequest, HttpMessage as _, HttpRequest, HttpResponse, ResponseError};
+use serde::Deserialize;
+use std::future::{ready, Ready};
+
+/// An extractor that verifies the token of the request and deserialises its payload.
+///
+/// The request is rejected with the response of the [`Error`] if the token is missing or invalid,
+/// or with a `500 Internal Server Error` if no [`TokenVerifier`] has been registered in the app data.
+#[derive(Debug, Clone)]
+pub struct Signed<T>(pub T);
+
+impl<T: for<'de> Deserialize<'de> + Payload> FromRequest for Signed<T> {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut RequestPayload) -> Self::Future {
+        ready(verify(req).map(Signed))
+    }
+}
+
+/// Verifies the token of the request and inserts its payload into the request extensions
+/// where it can be extracted via `actix_web::web::ReqData<T>`.
+///
+/// Sample Usage:
+/// ```rust
+/// use actix_web::{middleware, web, App};
+/// use hmac_serialiser::actix::require_token;
+/// use hmac_serialiser::web::TokenVerifier;
+/// use hmac_serialiser::{Algorithm, Encoder, HmacSigner, KeyInfo, Payload};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize, Debug, Clone)]
+/// struct UserData {
+///     username: String,
+/// }
+/// impl Payload for UserData {
+///     fn get_exp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+///         None
+///     }
+/// }
+///
+/// let key_info = KeyInfo {
+///     key: b"your_secret_key".to_vec(),
+///     salt: vec![],
+///     info: vec![],
+/// };
+/// let signer = HmacSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+///
+/// let app = App::new()
+///     .app_data(TokenVerifier::new(signer))
+///     .wrap(middleware::from_fn(require_token::<UserData>))
+///     .route(
+///         "/profile",
+///         web::get().to(|user: web::ReqData<UserData>| async move { user.into_inner().username }),
+///     );
+/// ```
+pub async fn require_token<T>(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, actix_web::Error>
+where
+    T: for<'de> Deserialize<'de> + Payload + 'static,
+{
+    let payload: T = verify(req.request())?;
+    req.extensions_mut().insert(payload);
+    next.call(req).await
+}
+
+fn verify<T: for<'de> Deserialize<'de> + Payload>(
+    req: &HttpRequest,
+) -> Result<T, actix_web::Error> {
+    let verifier = req
+        .app_data::<TokenVerifier>()
+        .or_else(|| {
+            req.app_data::<web::Data<TokenVerifier>>()
+                .map(|data| data.get_ref())
+        })
+        .ok_or_else(|| {
+            actix_web::error::ErrorInternalServerError("TokenVerifier has not been registered")
+        })?;
+    let query = Some(req.query_string()).filter(|query| !query.is_empty());
+    let payload = verifier.verify(
+        |name| {
+            req.headers()
+                .get_all(name)
+                .filter_map(|value| value.to_str().ok())
+                .collect()
+        },
+        query,
+    )?;
+    Ok(payload)
+}
+
+impl ResponseError for Error {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::from_u16(Error::status_code(self)).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(ResponseError::status_code(self))
+            .insert_header((header::CONTENT_TYPE, "application/json"))
+            .body(self.response_body())
+    }
+}
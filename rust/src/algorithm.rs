@@ -1,5 +1,8 @@
 #[cfg(feature = "ring")]
-use ring::{hkdf, hmac};
+use ring::{digest, hkdf, hmac};
+
+#[cfg(not(feature = "ring"))]
+use sha2::Digest as _;
 
 #[derive(Default, Clone, Debug)]
 pub enum Algorithm {
@@ -21,6 +24,38 @@ impl Algorithm {
         }
     }
 
+    /// Returns the block size of the underlying hash function in bytes.
+    #[inline]
+    pub fn block_size(&self) -> usize {
+        match self {
+            Algorithm::SHA1 | Algorithm::SHA256 => 64,
+            Algorithm::SHA384 | Algorithm::SHA512 => 128,
+        }
+    }
+
+    /// Hashes the data using the underlying hash function.
+    #[cfg(not(feature = "ring"))]
+    pub fn digest(&self, data: &[u8]) -> Vec<u8> {
+        match self {
+            Algorithm::SHA1 => sha1::Sha1::digest(data).to_vec(),
+            Algorithm::SHA256 => sha2::Sha256::digest(data).to_vec(),
+            Algorithm::SHA384 => sha2::Sha384::digest(data).to_vec(),
+            Algorithm::SHA512 => sha2::Sha512::digest(data).to_vec(),
+        }
+    }
+
+    /// Hashes the data using the underlying hash function.
+    #[cfg(feature = "ring")]
+    pub fn digest(&self, data: &[u8]) -> Vec<u8> {
+        let algo = match self {
+            Algorithm::SHA1 => &digest::SHA1_FOR_LEGACY_USE_ONLY,
+            Algorithm::SHA256 => &digest::SHA256,
+            Algorithm::SHA384 => &digest::SHA384,
+            Algorithm::SHA512 => &digest::SHA512,
+        };
+        digest::digest(algo, data).as_ref().to_vec()
+    }
+
     #[cfg(feature = "ring")]
     pub fn to_hmac(&self) -> hmac::Algorithm {
         match self {
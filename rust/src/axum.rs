@@ -0,0 +1,128 @@
+//! Axum integration, enabled via the `axum` feature.
+//!
+//! The [`Signed`] extractor verifies the token of a request with the [`TokenVerifier`] in the router's state,
+//! while [`require_token`] can be used with `axum::middleware::from_fn_with_state` to reject requests
+//! with invalid tokens before the handlers run. Errors are turned into JSON responses via [`Error::status_code`].
+//!
+//! Sample Usage:
+//! ```rust
+//! use axum::{routing::get, Router};
+//! use hmac_serialiser::axum::Signed;
+//! use hmac_serialiser::web::{TokenLocation, TokenVerifier};
+//! use hmac_serialiser::{Algorithm, Encoder, HmacSigner, KeyInfo, Payload};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, Debug)]
+//! struct UserData {
+//!     username: String,
+//! }
+//! impl Payload for UserData {
+//!     fn get_exp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+//!         None
+//!     }
+//! }
+//!
+//! async fn profile(Signed(user): Signed<UserData>) -> String {
+//!     user.username
+//! }
+//!
+//! let key_info = KeyInfo {
+//!     key: b"your_secret_key".to_vec(),
+//!     salt: vec![],
+//!     info: vec![],
+//! };
+//! let signer = HmacSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+//! let verifier = TokenVerifier::new(signer).with_location(TokenLocation::Cookie("session".to_string()));
+//!
+//! let app: Router = Router::new()
+//!     .route("/profile", get(profile))
+//!     .with_state(verifier);
+//! ```
+use crate::web::TokenVerifier;
+use crate::{Error, Payload};
+use ::axum::extract::{FromRef, FromRequestParts, Request, State};
+use ::axum::http::{header, request::Parts, StatusCode};
+use ::axum::middleware::Next;
+use ::axum::response::{IntoResponse, Response};
+use serde::Deserialize;
+
+/// An extractor that verifies the token of the request and deserialises its payload.
+///
+/// The [`TokenVerifier`] is taken from the router's state, which can either be the verifier
+/// itself or any state implementing `FromRef` for it.
+/// The request is rejected with the response of the [`Error`] if the token is missing or invalid.
+#[derive(Debug, Clone)]
+pub struct Signed<T>(pub T);
+
+impl<S, T> FromRequestParts<S> for Signed<T>
+where
+    TokenVerifier: FromRef<S>,
+    S: Send + Sync,
+    T: for<'de> Deserialize<'de> + Payload + Send,
+{
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let verifier = TokenVerifier::from_ref(state);
+        verifier.verify_http(&parts.headers, &parts.uri).map(Signed)
+    }
+}
+
+/// Verifies the token of the request and inserts its payload into the request extensions
+/// where it can be extracted via `axum::Extension<T>`.
+///
+/// Sample Usage:
+/// ```rust
+/// use axum::{middleware, routing::get, Extension, Router};
+/// use hmac_serialiser::axum::require_token;
+/// use hmac_serialiser::web::TokenVerifier;
+/// use hmac_serialiser::{Algorithm, Encoder, HmacSigner, KeyInfo, Payload};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize, Debug, Clone)]
+/// struct UserData {
+///     username: String,
+/// }
+/// impl Payload for UserData {
+///     fn get_exp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+///         None
+///     }
+/// }
+///
+/// let key_info = KeyInfo {
+///     key: b"your_secret_key".to_vec(),
+///     salt: vec![],
+///     info: vec![],
+/// };
+/// let signer = HmacSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+/// let verifier = TokenVerifier::new(signer);
+///
+/// let app: Router = Router::new()
+///     .route("/profile", get(|Extension(user): Extension<UserData>| async move { user.username }))
+///     .layer(middleware::from_fn_with_state(verifier, require_token::<UserData>));
+/// ```
+pub async fn require_token<T>(
+    State(verifier): State<TokenVerifier>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, Error>
+where
+    T: for<'de> Deserialize<'de> + Payload + Clone + Send + Sync + 'static,
+{
+    let payload: T = verifier.verify_http(request.headers(), request.uri())?;
+    request.extensions_mut().insert(payload);
+    Ok(next.run(request).await)
+}
+
+impl IntoResponse for Error {
+    fn into_response(self) -> Response {
+        let status =
+            StatusCode::from_u16(self.status_code()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        (
+            status,
+            [(header::CONTENT_TYPE, "application/json")],
+            self.response_body(),
+        )
+            .into_response()
+    }
+}
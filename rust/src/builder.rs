@@ -0,0 +1,152 @@
+use crate::{Algorithm, Clock, Encoder, Error, HmacSigner, KeyInfo, PayloadFormat};
+use std::sync::Arc;
+use zeroize::Zeroizing;
+
+/// A builder for configuring an [`HmacSigner`] fluently.
+///
+/// Every option is validated when [`HmacSignerBuilder::build`] is called,
+/// so a misconfigured signer is reported as an error instead of a panic.
+/// Like [`KeyInfo`], the key is wiped from memory once the builder is dropped.
+///
+/// Sample Usage:
+/// ```rust
+/// use hmac_serialiser::{Algorithm, Encoder, HmacSigner, PayloadFormat};
+///
+/// let signer = HmacSigner::builder()
+///     .key(b"your_secret_key")
+///     .salt(b"your_salt")
+///     .info(b"password-reset")
+///     .algorithm(Algorithm::SHA512)
+///     .encoder(Encoder::UrlSafeNoPadding)
+///     .separator("~")
+///     .leeway(chrono::Duration::seconds(5))
+///     .format(PayloadFormat::Json)
+///     .build()
+///     .unwrap();
+/// let token = signer.sign_str("user123");
+/// assert!(token.contains('~'));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct HmacSignerBuilder {
+    key_info: Zeroizing<KeyInfo>,
+    algo: Algorithm,
+    encoder: Encoder,
+    sep: Option<String>,
+    min_key_length: usize,
+    max_decompressed_size: Option<usize>,
+    format: PayloadFormat,
+    leeway: chrono::Duration,
+    clock: Option<Arc<dyn Clock>>,
+}
+
+impl HmacSignerBuilder {
+    /// Sets the main secret key.
+    pub fn key(mut self, key: &[u8]) -> Self {
+        self.key_info.key = key.to_vec();
+        self
+    }
+
+    /// Sets the salt for the key expansion.
+    pub fn salt(mut self, salt: &[u8]) -> Self {
+        self.key_info.salt = salt.to_vec();
+        self
+    }
+
+    /// Sets the application specific info for the key expansion.
+    pub fn info(mut self, info: &[u8]) -> Self {
+        self.key_info.info = info.to_vec();
+        self
+    }
+
+    /// Sets the key, salt and info at once, replacing any of them set previously.
+    pub fn key_info(mut self, key_info: KeyInfo) -> Self {
+        self.key_info = Zeroizing::new(key_info);
+        self
+    }
+
+    /// Sets the HMAC algorithm, which is SHA256 by default.
+    pub fn algorithm(mut self, algo: Algorithm) -> Self {
+        self.algo = algo;
+        self
+    }
+
+    /// Sets the base64 encoder, which is URL-safe without padding by default.
+    pub fn encoder(mut self, encoder: Encoder) -> Self {
+        self.encoder = encoder;
+        self
+    }
+
+    /// Sets the separator between the payload and the signature, see [`HmacSigner::try_new_with_separator`].
+    pub fn separator(mut self, sep: &str) -> Self {
+        self.sep = Some(sep.to_string());
+        self
+    }
+
+    /// Sets the minimum key length, see [`HmacSigner::try_new_with_min_key_length`].
+    pub fn min_key_length(mut self, min_key_length: usize) -> Self {
+        self.min_key_length = min_key_length;
+        self
+    }
+
+    /// Enables compression, see [`HmacSigner::with_compression`].
+    pub fn compression(mut self, max_decompressed_size: usize) -> Self {
+        self.max_decompressed_size = Some(max_decompressed_size);
+        self
+    }
+
+    /// Sets the payload format, see [`HmacSigner::with_format`].
+    pub fn format(mut self, format: PayloadFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Sets the tolerance for clock drift, see [`HmacSigner::with_leeway`].
+    pub fn leeway(mut self, leeway: chrono::Duration) -> Self {
+        self.leeway = leeway;
+        self
+    }
+
+    /// Sets the clock used when checking the expiration time, see [`HmacSigner::with_clock`].
+    pub fn clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Some(Arc::new(clock));
+        self
+    }
+
+    /// Validates the options and initialises the signer.
+    ///
+    /// An `InvalidSeparator` error is returned if the separator cannot be used with the encoder
+    /// and an `InvalidInput` error is returned if the leeway is negative or greater than [`MAX_LEEWAY`](crate::MAX_LEEWAY).
+    /// The key is validated in the same way as [`HmacSigner::try_new_with_min_key_length`].
+    pub fn build(mut self) -> Result<HmacSigner, Error> {
+        if let Some(sep) = &self.sep {
+            if !self.encoder.is_valid_separator(sep) {
+                return Err(Error::InvalidSeparator(sep.clone()));
+            }
+        }
+        HmacSigner::validate_leeway(self.leeway)?;
+
+        let mut signer = HmacSigner::try_new_with_min_key_length(
+            std::mem::take(&mut *self.key_info),
+            self.min_key_length,
+            self.algo,
+            self.encoder,
+        )?;
+        if let Some(sep) = self.sep {
+            signer.sep = sep;
+        }
+        signer.max_decompressed_size = self.max_decompressed_size;
+        signer.format = self.format;
+        signer.leeway = self.leeway;
+        if let Some(clock) = self.clock {
+            signer.clock = clock;
+        }
+        Ok(signer)
+    }
+}
+
+impl HmacSigner {
+    /// Returns a builder for configuring the signer, see [`HmacSignerBuilder`].
+    pub fn builder() -> HmacSignerBuilder {
+        HmacSignerBuilder::default()
+    }
+}
@@ -0,0 +1,110 @@
+use crate::Error;
+use std::sync::{Arc, Mutex};
+
+/// A trait for the source of the current time used when checking the time claims of a token.
+///
+/// [`SystemClock`] is used by default. A custom clock can be used to check tokens against
+/// a trusted time source, or [`FixedClock`] can be used to test expiry logic deterministically.
+pub trait Clock: std::fmt::Debug + Send + Sync {
+    fn now(&self) -> chrono::DateTime<chrono::Utc>;
+}
+
+/// A clock that returns the system time.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> chrono::DateTime<chrono::Utc> {
+        chrono::Utc::now()
+    }
+}
+
+/// A clock that always returns the same time until it is changed via [`FixedClock::set`] or [`FixedClock::advance`].
+///
+/// Clones of the clock share the same time, so the time can still be changed
+/// after a clone has been passed to a signer.
+///
+/// Sample Usage:
+/// ```rust
+/// use hmac_serialiser::{Algorithm, Encoder, Error, FixedClock, HmacSigner, KeyInfo, Payload};
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Serialize, Deserialize, Debug)]
+/// struct UserData {
+///     username: String,
+///     exp: chrono::DateTime<chrono::Utc>,
+/// }
+/// impl Payload for UserData {
+///     fn get_exp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+///         Some(self.exp)
+///     }
+/// }
+///
+/// let key_info = KeyInfo {
+///     key: b"your_secret_key".to_vec(),
+///     salt: vec![],
+///     info: vec![],
+/// };
+/// let clock = FixedClock::new(chrono::Utc::now());
+/// let signer = HmacSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding)
+///     .with_clock(clock.clone());
+///
+/// let user = UserData {
+///     username: "user123".to_string(),
+///     exp: clock.now() + chrono::Duration::minutes(15),
+/// };
+/// let token = signer.sign(&user);
+/// assert!(signer.unsign::<UserData>(&token).is_ok());
+///
+/// clock.advance(chrono::Duration::minutes(16));
+/// assert_eq!(signer.unsign::<UserData>(&token).unwrap_err(), Error::TokenExpired);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FixedClock {
+    time: Arc<Mutex<chrono::DateTime<chrono::Utc>>>,
+}
+
+impl FixedClock {
+    pub fn new(time: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            time: Arc::new(Mutex::new(time)),
+        }
+    }
+
+    /// Returns the current time of the clock.
+    pub fn now(&self) -> chrono::DateTime<chrono::Utc> {
+        *self.time.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Sets the current time of the clock.
+    pub fn set(&self, time: chrono::DateTime<chrono::Utc>) {
+        *self.time.lock().unwrap_or_else(|e| e.into_inner()) = time;
+    }
+
+    /// Moves the current time of the clock forward, or backward if the duration is negative.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resulting time is out of range, see [`FixedClock::try_advance`] for a non-panicking alternative.
+    pub fn advance(&self, duration: chrono::Duration) {
+        self.try_advance(duration)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Moves the current time of the clock forward, or backward if the duration is negative.
+    ///
+    /// An `InvalidInput` error is returned and the time is left unchanged if the resulting time is out of range.
+    pub fn try_advance(&self, duration: chrono::Duration) -> Result<(), Error> {
+        let mut time = self.time.lock().unwrap_or_else(|e| e.into_inner());
+        *time = time
+            .checked_add_signed(duration)
+            .ok_or_else(|| Error::InvalidInput("clock time is out of range".to_string()))?;
+        Ok(())
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> chrono::DateTime<chrono::Utc> {
+        FixedClock::now(self)
+    }
+}
@@ -0,0 +1,34 @@
+use crate::Error;
+use flate2::{read::ZlibDecoder, write::ZlibEncoder, Compression};
+use std::io::{Read as _, Write as _};
+
+/// The prefix marking an encoded payload as compressed, following ItsDangerous' convention.
+pub(crate) const PREFIX: char = '.';
+
+/// Zlib compresses the data, returning `None` if compressing does not make
+/// the data shorter after accounting for the leading `.` that marks it as compressed.
+pub(crate) fn compress(data: &[u8]) -> Option<Vec<u8>> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::new(6));
+    let compressed = encoder
+        .write_all(data)
+        .and_then(|_| encoder.finish())
+        .ok()?;
+    (!compressed.is_empty() && compressed.len() + 1 < data.len()).then_some(compressed)
+}
+
+/// Decompresses the zlib compressed data, reading at most `max_size` bytes of output
+/// so that a small but highly compressed payload cannot exhaust the memory.
+///
+/// An `InvalidPayload` error is returned if the data is not zlib compressed and
+/// a `PayloadTooLarge` error is returned if the decompressed data exceeds `max_size` bytes.
+pub(crate) fn decompress(data: &[u8], max_size: usize) -> Result<Vec<u8>, Error> {
+    let mut decompressed = Vec::new();
+    ZlibDecoder::new(data)
+        .take(max_size as u64 + 1)
+        .read_to_end(&mut decompressed)
+        .map_err(|_| Error::InvalidPayload)?;
+    if decompressed.len() > max_size {
+        return Err(Error::PayloadTooLarge(max_size));
+    }
+    Ok(decompressed)
+}
@@ -0,0 +1,221 @@
+//! Signed cookies via the `cookie` crate, enabled via the `cookie` feature.
+//!
+//! [`CookieSigner`] writes `Payload` values into cookies with secure attributes by default and
+//! derives their `Max-Age` and `Expires` attributes from [`Payload::get_exp`], while [`SignedCookieJar`]
+//! reads and writes such cookies in a `cookie::CookieJar`.
+//!
+//! Sample Usage:
+//! ```rust
+//! use cookie::CookieJar;
+//! use hmac_serialiser::cookie::CookieSigner;
+//! use hmac_serialiser::{Algorithm, Encoder, HmacSigner, KeyInfo, Payload};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, Debug)]
+//! struct Session {
+//!     username: String,
+//!     exp: chrono::DateTime<chrono::Utc>,
+//! }
+//! impl Payload for Session {
+//!     fn get_exp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+//!         Some(self.exp)
+//!     }
+//! }
+//!
+//! let key_info = KeyInfo {
+//!     key: b"your_secret_key".to_vec(),
+//!     salt: vec![],
+//!     info: vec![],
+//! };
+//! let signer = HmacSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+//! let cookie_signer = CookieSigner::new(signer);
+//!
+//! let mut jar = CookieJar::new();
+//! let session = Session {
+//!     username: "user123".to_string(),
+//!     exp: chrono::Utc::now() + chrono::Duration::days(7),
+//! };
+//! cookie_signer.jar(&mut jar).add("session", &session).unwrap();
+//!
+//! let session: Session = cookie_signer.jar(&mut jar).get("session").unwrap().unwrap();
+//! assert_eq!(session.username, "user123");
+//! ```
+use crate::{Error, HmacSigner, Payload};
+use ::cookie::time::{Duration, OffsetDateTime};
+use ::cookie::{Cookie, CookieJar, SameSite};
+use serde::{Deserialize, Serialize};
+
+/// The default maximum size in bytes of a cookie's name and value, which is the limit enforced by most browsers.
+pub const DEFAULT_MAX_COOKIE_SIZE: usize = 4096;
+
+/// A struct that signs payloads into cookies and verifies them.
+///
+/// Cookies are `HttpOnly`, `Secure`, `SameSite=Lax` and scoped to the `/` path by default.
+#[derive(Debug, Clone)]
+pub struct CookieSigner {
+    signer: HmacSigner,
+    http_only: bool,
+    secure: bool,
+    same_site: SameSite,
+    path: Option<String>,
+    domain: Option<String>,
+    max_size: usize,
+}
+
+impl CookieSigner {
+    pub fn new(signer: HmacSigner) -> Self {
+        Self {
+            signer,
+            http_only: true,
+            secure: true,
+            same_site: SameSite::Lax,
+            path: Some("/".to_string()),
+            domain: None,
+            max_size: DEFAULT_MAX_COOKIE_SIZE,
+        }
+    }
+
+    /// Sets whether the cookies are hidden from JavaScript, which is `true` by default.
+    pub fn with_http_only(mut self, http_only: bool) -> Self {
+        self.http_only = http_only;
+        self
+    }
+
+    /// Sets whether the cookies are only sent over HTTPS, which is `true` by default.
+    ///
+    /// This can be disabled for local development over plain HTTP.
+    pub fn with_secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    /// Sets the `SameSite` attribute of the cookies, which is `Lax` by default.
+    pub fn with_same_site(mut self, same_site: SameSite) -> Self {
+        self.same_site = same_site;
+        self
+    }
+
+    /// Sets the `Path` attribute of the cookies, which is `/` by default.
+    pub fn with_path(mut self, path: &str) -> Self {
+        self.path = Some(path.to_string());
+        self
+    }
+
+    /// Sets the `Domain` attribute of the cookies, which is not set by default.
+    pub fn with_domain(mut self, domain: &str) -> Self {
+        self.domain = Some(domain.to_string());
+        self
+    }
+
+    /// Sets the maximum size in bytes of a cookie's name and value, see [`DEFAULT_MAX_COOKIE_SIZE`].
+    pub fn with_max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Returns the signer used to sign and verify the cookies.
+    pub fn signer(&self) -> &HmacSigner {
+        &self.signer
+    }
+
+    fn check_size(&self, name: &str, value: &str) -> Result<(), Error> {
+        if name.len() + value.len() > self.max_size {
+            return Err(Error::CookieTooLarge(self.max_size));
+        }
+        Ok(())
+    }
+
+    /// Signs the payload into a cookie with the configured attributes.
+    ///
+    /// If the payload has an expiration time, the `Max-Age` and `Expires` attributes are set to match it.
+    /// Otherwise, a session cookie is returned.
+    ///
+    /// A `CookieTooLarge` error is returned if the cookie exceeds the maximum size.
+    /// The other errors are the same as the ones returned by [`HmacSigner::try_sign`].
+    pub fn cookie<T: Serialize + Payload>(
+        &self,
+        name: &str,
+        payload: &T,
+    ) -> Result<Cookie<'static>, Error> {
+        let token = self.signer.try_sign(payload)?;
+        self.check_size(name, &token)?;
+
+        let mut cookie = Cookie::build((name.to_string(), token))
+            .http_only(self.http_only)
+            .secure(self.secure)
+            .same_site(self.same_site)
+            .build();
+        if let Some(path) = &self.path {
+            cookie.set_path(path.clone());
+        }
+        if let Some(domain) = &self.domain {
+            cookie.set_domain(domain.clone());
+        }
+        if let Some(expiry) = payload.get_exp() {
+            let max_age = (expiry - self.signer.now()).num_seconds().max(0);
+            cookie.set_max_age(Duration::seconds(max_age));
+            if let Ok(expires) = OffsetDateTime::from_unix_timestamp(expiry.timestamp()) {
+                cookie.set_expires(expires);
+            }
+        }
+        Ok(cookie)
+    }
+
+    /// Verifies the cookie's value and returns the deserialised payload.
+    ///
+    /// A `CookieTooLarge` error is returned before verifying the cookie if it exceeds the maximum size.
+    /// The other errors are the same as the ones returned by [`HmacSigner::unsign`].
+    pub fn verify<T: for<'de> Deserialize<'de> + Payload>(
+        &self,
+        cookie: &Cookie<'_>,
+    ) -> Result<T, Error> {
+        self.check_size(cookie.name(), cookie.value())?;
+        self.signer.unsign(cookie.value())
+    }
+
+    /// Wraps the cookie jar so that the cookies are signed and verified when they are added and retrieved.
+    pub fn jar<'a>(&'a self, jar: &'a mut CookieJar) -> SignedCookieJar<'a> {
+        SignedCookieJar { signer: self, jar }
+    }
+}
+
+/// A wrapper around a `cookie::CookieJar` that signs and verifies its cookies via a [`CookieSigner`].
+///
+/// Changes made through the wrapper are tracked by the underlying jar,
+/// so that `CookieJar::delta` can be used to build the `Set-Cookie` headers.
+#[derive(Debug)]
+pub struct SignedCookieJar<'a> {
+    signer: &'a CookieSigner,
+    jar: &'a mut CookieJar,
+}
+
+impl SignedCookieJar<'_> {
+    /// Signs the payload into a cookie and adds it to the jar, see [`CookieSigner::cookie`].
+    pub fn add<T: Serialize + Payload>(&mut self, name: &str, payload: &T) -> Result<(), Error> {
+        let cookie = self.signer.cookie(name, payload)?;
+        self.jar.add(cookie);
+        Ok(())
+    }
+
+    /// Verifies the cookie with the given name and returns the deserialised payload,
+    /// or `None` if there is no such cookie, see [`CookieSigner::verify`].
+    pub fn get<T: for<'de> Deserialize<'de> + Payload>(
+        &self,
+        name: &str,
+    ) -> Option<Result<T, Error>> {
+        self.jar.get(name).map(|cookie| self.signer.verify(cookie))
+    }
+
+    /// Removes the cookie with the given name, using the configured path and domain
+    /// so that the browser also deletes it.
+    pub fn remove(&mut self, name: &str) {
+        let mut cookie = Cookie::from(name.to_string());
+        if let Some(path) = &self.signer.path {
+            cookie.set_path(path.clone());
+        }
+        if let Some(domain) = &self.signer.domain {
+            cookie.set_domain(domain.clone());
+        }
+        self.jar.remove(cookie);
+    }
+}
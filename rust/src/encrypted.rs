@@ -0,0 +1,221 @@
+use crate::{hkdf, Algorithm, Clock, Encoder, Error, HmacSigner, KeyInfo, Payload, DELIM};
+use base64::Engine as _;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+#[cfg(not(feature = "ring"))]
+use chacha20poly1305::{aead::Aead as _, AeadCore as _, ChaCha20Poly1305, KeyInit as _};
+
+#[cfg(feature = "ring")]
+use ring::{aead, rand::SecureRandom as _};
+
+// appended to the key's info so that the encryption and MAC keys are independent of each other
+// and of the key that `HmacSigner` would derive from the same `KeyInfo`
+const ENCRYPTION_INFO: &[u8] = b"hmac-serialiser.encryption";
+const AUTHENTICATION_INFO: &[u8] = b"hmac-serialiser.authentication";
+
+const KEY_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 12;
+
+/// A struct that holds the encrypted signer logic.
+///
+/// The `EncryptedSigner` struct encrypts the payload with ChaCha20-Poly1305 before signing it with HMAC,
+/// producing an output structure of `<nonce + ciphertext>.<signature>`.
+/// Unlike [`HmacSigner`], the payload cannot be read by the client by simply base64 decoding the token.
+///
+/// Separate encryption and MAC keys are derived from the same `KeyInfo` using HKDF.
+/// The token format is the same for both the `rust_crypto` and the `ring` features.
+///
+/// Sample Usage:
+/// ```rust
+/// use hmac_serialiser::{Algorithm, Encoder, EncryptedSigner, KeyInfo, Payload};
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Serialize, Deserialize, Debug)]
+/// struct UserData {
+///     email: String,
+/// }
+/// impl Payload for UserData {
+///     fn get_exp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+///         None
+///     }
+/// }
+///
+/// let key_info = KeyInfo {
+///     key: b"your_secret_key".to_vec(),
+///     salt: b"your_salt".to_vec(),
+///     info: vec![],
+/// };
+/// let signer = EncryptedSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+/// let token = signer.sign(&UserData { email: "user123@example.com".to_string() });
+/// assert!(!token.contains("user123"));
+///
+/// let user: UserData = signer.unsign(&token).unwrap();
+/// assert_eq!(user.email, "user123@example.com");
+/// ```
+#[derive(Clone)]
+pub struct EncryptedSigner {
+    encryption_key: Zeroizing<Vec<u8>>,
+    signer: HmacSigner,
+}
+
+impl std::fmt::Debug for EncryptedSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EncryptedSigner")
+            .field("signer", &self.signer)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EncryptedSigner {
+    /// Initialises the signer, panicking if the key is invalid.
+    ///
+    /// This is a convenience wrapper around [`EncryptedSigner::try_new`].
+    pub fn new(key_info: KeyInfo, algo: Algorithm, encoder: Encoder) -> Self {
+        Self::try_new(key_info, algo, encoder).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Initialises the signer, returning an error if the key is invalid.
+    ///
+    /// The key is validated in the same way as [`HmacSigner::try_new`].
+    pub fn try_new(key_info: KeyInfo, algo: Algorithm, encoder: Encoder) -> Result<Self, Error> {
+        let key_info = Zeroizing::new(key_info);
+        let mac_key_info = KeyInfo {
+            key: key_info.key.clone(),
+            salt: key_info.salt.clone(),
+            info: [key_info.info.as_slice(), AUTHENTICATION_INFO].concat(),
+        };
+        let signer = HmacSigner::try_new(mac_key_info, algo.clone(), encoder)?;
+
+        let encryption_key = hkdf::HkdfWrapper::new(algo).expand_to_length(
+            &key_info.key,
+            &key_info.salt,
+            &[key_info.info.as_slice(), ENCRYPTION_INFO].concat(),
+            KEY_LENGTH,
+        )?;
+        Ok(Self {
+            encryption_key,
+            signer,
+        })
+    }
+
+    /// Sets the clock used to get the current time when checking the expiration time of a token,
+    /// see [`HmacSigner::with_clock`].
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.signer = self.signer.with_clock(clock);
+        self
+    }
+
+    /// Sets the tolerance for clock drift when checking the expiration time of a token,
+    /// see [`HmacSigner::with_leeway`].
+    pub fn with_leeway(mut self, leeway: chrono::Duration) -> Self {
+        self.signer = self.signer.with_leeway(leeway);
+        self
+    }
+
+    /// Encrypts the plaintext and returns the random nonce followed by the ciphertext.
+    #[cfg(not(feature = "ring"))]
+    fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, Error> {
+        let cipher = ChaCha20Poly1305::new_from_slice(&self.encryption_key)
+            .map_err(|_| Error::EncryptionError)?;
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut chacha20poly1305::aead::OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, plaintext)
+            .map_err(|_| Error::EncryptionError)?;
+        Ok([nonce.as_slice(), &ciphertext].concat())
+    }
+
+    /// Decrypts the nonce followed by the ciphertext and returns the plaintext.
+    #[cfg(not(feature = "ring"))]
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, Error> {
+        if data.len() < NONCE_LENGTH {
+            return Err(Error::DecryptionError);
+        }
+        let (nonce, ciphertext) = data.split_at(NONCE_LENGTH);
+        let cipher = ChaCha20Poly1305::new_from_slice(&self.encryption_key)
+            .map_err(|_| Error::DecryptionError)?;
+        cipher
+            .decrypt(nonce.into(), ciphertext)
+            .map_err(|_| Error::DecryptionError)
+    }
+
+    /// Encrypts the plaintext and returns the random nonce followed by the ciphertext.
+    #[cfg(feature = "ring")]
+    fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, Error> {
+        let key = aead::UnboundKey::new(&aead::CHACHA20_POLY1305, &self.encryption_key)
+            .map_err(|_| Error::EncryptionError)?;
+        let mut nonce = [0u8; NONCE_LENGTH];
+        ring::rand::SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| Error::EncryptionError)?;
+
+        let mut in_out = plaintext.to_vec();
+        aead::LessSafeKey::new(key)
+            .seal_in_place_append_tag(
+                aead::Nonce::assume_unique_for_key(nonce),
+                aead::Aad::empty(),
+                &mut in_out,
+            )
+            .map_err(|_| Error::EncryptionError)?;
+        Ok([nonce.as_slice(), &in_out].concat())
+    }
+
+    /// Decrypts the nonce followed by the ciphertext and returns the plaintext.
+    #[cfg(feature = "ring")]
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, Error> {
+        if data.len() < NONCE_LENGTH {
+            return Err(Error::DecryptionError);
+        }
+        let (nonce, ciphertext) = data.split_at(NONCE_LENGTH);
+        let nonce =
+            aead::Nonce::try_assume_unique_for_key(nonce).map_err(|_| Error::DecryptionError)?;
+        let key = aead::UnboundKey::new(&aead::CHACHA20_POLY1305, &self.encryption_key)
+            .map_err(|_| Error::DecryptionError)?;
+
+        let mut in_out = ciphertext.to_vec();
+        let plaintext = aead::LessSafeKey::new(key)
+            .open_in_place(nonce, aead::Aad::empty(), &mut in_out)
+            .map_err(|_| Error::DecryptionError)?;
+        Ok(plaintext.to_vec())
+    }
+
+    /// Encrypts and signs the payload and returns the token which can be sent to the client.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the payload cannot be serialised or encrypted, see [`EncryptedSigner::try_sign`] for a non-panicking alternative.
+    pub fn sign<T: Serialize + Payload>(&self, payload: &T) -> String {
+        self.try_sign(payload).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Encrypts and signs the payload and returns the token.
+    ///
+    /// A `SerialisationError` is returned if the payload cannot be serialised and
+    /// an `EncryptionError` is returned if the payload cannot be encrypted.
+    pub fn try_sign<T: Serialize + Payload>(&self, payload: &T) -> Result<String, Error> {
+        let json =
+            serde_json::to_vec(payload).map_err(|e| Error::SerialisationError(e.to_string()))?;
+        let token = self.signer.encoder.encode(self.encrypt(&json)?);
+        let signature = self.signer.sign_payload(token.as_bytes());
+        let signature = self.signer.encoder.encode(&signature);
+        Ok(format!("{}{}{}", token, DELIM, signature))
+    }
+
+    /// Verifies and decrypts the token and returns the deserialised payload.
+    ///
+    /// The signature is verified before the payload is decrypted.
+    /// If the verified payload cannot be decrypted, a `DecryptionError` is returned.
+    /// The other errors are the same as the ones returned by [`HmacSigner::unsign`].
+    pub fn unsign<T: for<'de> Deserialize<'de> + Payload>(&self, token: &str) -> Result<T, Error> {
+        let encoded_payload = self.signer.verify_token(token)?;
+        let encrypted_payload = self
+            .signer
+            .encoder
+            .decode(encoded_payload)
+            .map_err(|_| Error::PayloadDecodeError)?;
+        let json = self.decrypt(&encrypted_payload)?;
+        let payload: T = serde_json::from_slice(&json).map_err(|_| Error::InvalidPayload)?;
+        self.signer.check_time_claims(&payload, self.signer.now())?;
+        Ok(payload)
+    }
+}
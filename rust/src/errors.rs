@@ -1,6 +1,10 @@
 use thiserror::Error;
 
+/// The errors returned by the signers.
+///
+/// The enum is non-exhaustive so that new errors can be added without a breaking change.
 #[derive(Debug, Error, PartialEq)]
+#[non_exhaustive]
 pub enum Error {
     #[error("invalid input: {0}")]
     InvalidInput(String),
@@ -16,4 +20,42 @@ pub enum Error {
     HkdfFillError,
     #[error("token has expired")]
     TokenExpired,
+    #[error("could not serialise payload: {0}")]
+    SerialisationError(String),
+    #[error("could not encrypt payload")]
+    EncryptionError,
+    #[error("could not decrypt payload")]
+    DecryptionError,
+    #[error("decompressed payload exceeds the maximum size of {0} bytes")]
+    PayloadTooLarge(usize),
+    #[error("could not read payload: {0}")]
+    IoError(String),
+    #[error("could not decode the payload, was it perhaps signed with a different encoder?")]
+    PayloadDecodeError,
+    #[error("payload is not valid utf-8")]
+    PayloadUtf8Error,
+    #[error("invalid separator: {0:?} cannot contain base64 characters")]
+    InvalidSeparator(String),
+    #[error("key cannot be empty")]
+    EmptyKey,
+    #[error("key is too short: expected at least {0} bytes")]
+    KeyTooShort(usize),
+    #[error("key is too weak as it consists of a single repeated byte")]
+    WeakKey,
+    #[error("invalid key id: {0:?} can only contain ASCII letters, digits, '-' and '_'")]
+    InvalidKeyId(String),
+    #[error("unknown key id: {0:?}")]
+    UnknownKeyId(String),
+    #[error("token is not valid yet")]
+    TokenNotYetValid,
+    #[error("token was issued in the future")]
+    TokenIssuedInFuture,
+    #[error("no token provided")]
+    MissingToken,
+    #[error("cookie exceeds the maximum size of {0} bytes")]
+    CookieTooLarge(usize),
+    #[error("session has been revoked")]
+    SessionRevoked,
+    #[error("could not access revocation store: {0}")]
+    RevocationStoreError(String),
 }
@@ -1,4 +1,6 @@
 use crate::algorithm::Algorithm;
+use crate::errors::Error;
+use zeroize::Zeroizing;
 
 #[cfg(not(feature = "ring"))]
 use hkdf::Hkdf;
@@ -6,18 +8,28 @@ use hkdf::Hkdf;
 #[cfg(feature = "ring")]
 use ring::hkdf;
 
+#[cfg(feature = "ring")]
+struct OkmLength(usize);
+
+#[cfg(feature = "ring")]
+impl hkdf::KeyType for OkmLength {
+    fn len(&self) -> usize {
+        self.0
+    }
+}
+
 pub struct HkdfWrapper {
     algo: Algorithm,
 }
 
 #[cfg(not(feature = "ring"))]
 macro_rules! hkdf_expand {
-    ($self:ident, $ikm:ident, $salt:ident, $info:ident, $D:ty) => {{
+    ($ikm:ident, $salt:ident, $info:ident, $length:ident, $D:ty) => {{
         let hk = Hkdf::<$D>::new(Some($salt), $ikm);
-        let mut okm = vec![0u8; $self.algo.output_length()];
+        let mut okm = Zeroizing::new(vec![0u8; $length]);
         hk.expand($info, &mut okm)
-            .expect("could not expand key due to possibly invalid length");
-        okm
+            .map_err(|_| Error::HkdfExpandError)?;
+        Ok(okm)
     }};
 }
 
@@ -26,27 +38,57 @@ impl HkdfWrapper {
         Self { algo }
     }
 
+    /// Expands the key to the hash function's output length.
+    ///
+    /// The expanded key is wiped from memory once it is dropped.
+    pub fn expand(
+        &self,
+        ikm: &[u8],
+        salt: &[u8],
+        info: &[u8],
+    ) -> Result<Zeroizing<Vec<u8>>, Error> {
+        self.expand_to_length(ikm, salt, info, self.algo.output_length())
+    }
+
+    /// Expands the key to the provided length in bytes.
+    ///
+    /// An `HkdfExpandError` is returned if the length exceeds 255 times the hash function's output length.
     #[cfg(not(feature = "ring"))]
-    pub fn expand(&self, ikm: &[u8], salt: &[u8], info: &[u8]) -> Vec<u8> {
+    pub fn expand_to_length(
+        &self,
+        ikm: &[u8],
+        salt: &[u8],
+        info: &[u8],
+        length: usize,
+    ) -> Result<Zeroizing<Vec<u8>>, Error> {
         match self.algo {
-            Algorithm::SHA1 => hkdf_expand!(self, ikm, salt, info, sha1::Sha1),
-            Algorithm::SHA256 => hkdf_expand!(self, ikm, salt, info, sha2::Sha256),
-            Algorithm::SHA384 => hkdf_expand!(self, ikm, salt, info, sha2::Sha384),
-            Algorithm::SHA512 => hkdf_expand!(self, ikm, salt, info, sha2::Sha512),
+            Algorithm::SHA1 => hkdf_expand!(ikm, salt, info, length, sha1::Sha1),
+            Algorithm::SHA256 => hkdf_expand!(ikm, salt, info, length, sha2::Sha256),
+            Algorithm::SHA384 => hkdf_expand!(ikm, salt, info, length, sha2::Sha384),
+            Algorithm::SHA512 => hkdf_expand!(ikm, salt, info, length, sha2::Sha512),
         }
     }
 
+    /// Expands the key to the provided length in bytes.
+    ///
+    /// An `HkdfExpandError` is returned if the length exceeds 255 times the hash function's output length.
     #[cfg(feature = "ring")]
-    pub fn expand(&self, ikm: &[u8], salt: &[u8], info: &[u8]) -> Vec<u8> {
+    pub fn expand_to_length(
+        &self,
+        ikm: &[u8],
+        salt: &[u8],
+        info: &[u8],
+        length: usize,
+    ) -> Result<Zeroizing<Vec<u8>>, Error> {
         let hkdf_algo = self.algo.to_hkdf();
         let prk = hkdf::Salt::new(hkdf_algo, salt).extract(ikm);
 
-        let mut okm = vec![0u8; self.algo.output_length()];
+        let mut okm = Zeroizing::new(vec![0u8; length]);
         let okm_slice = &mut okm[..];
-        prk.expand(&[info], self.algo.to_hmac())
-            .expect("could not expand key due to possibly invalid length")
+        prk.expand(&[info], OkmLength(length))
+            .map_err(|_| Error::HkdfExpandError)?
             .fill(okm_slice)
-            .expect("could not fill key due to possibly invalid length");
-        okm
+            .map_err(|_| Error::HkdfFillError)?;
+        Ok(okm)
     }
 }
@@ -0,0 +1,543 @@
+//! Compatibility with Python's [ItsDangerous](https://github.com/pallets/itsdangerous) library.
+//!
+//! Unlike [`HmacSigner`](crate::HmacSigner), ItsDangerous does not use HKDF to derive the signing key,
+//! uses a different timestamp encoding, and zlib compresses the payload of URL-safe serialisers when it
+//! makes the payload shorter. The types in this module reproduce the ItsDangerous formats so that tokens
+//! produced by a Python application, like Flask session cookies, can be verified with the same secret and salt.
+//!
+//! | ItsDangerous             | Rust                     |
+//! |--------------------------|--------------------------|
+//! | `Signer`                 | `Signer`                 |
+//! | `TimestampSigner`        | `TimestampSigner`        |
+//! | `Serializer`             | `Serializer`             |
+//! | `URLSafeSerializer`      | `UrlSafeSerializer`      |
+//! | `URLSafeTimedSerializer` | `UrlSafeTimedSerializer` |
+//!
+//! Note: the compressed payloads are produced by a different deflate implementation than Python's zlib module,
+//! hence they may not be byte for byte identical to the ones produced by ItsDangerous but are still accepted by it.
+//!
+//! ItsDangerous also uses different default salts for its signers and serialisers
+//! which are available as [`SIGNER_SALT`] and [`SERIALIZER_SALT`].
+//!
+//! Sample Usage:
+//! ```rust
+//! use hmac_serialiser::itsdangerous::UrlSafeTimedSerializer;
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Session {
+//!     username: String,
+//! }
+//!
+//! // app.secret_key = "your_secret_key"
+//! let serializer = UrlSafeTimedSerializer::flask_session(b"your_secret_key")
+//!     .expect("key should not be empty");
+//! let cookie = serializer.dumps(&serde_json::json!({ "username": "user123" }));
+//!
+//! let session: Session = serializer
+//!     .loads(&cookie, Some(chrono::Duration::days(31)))
+//!     .expect("cookie should be valid");
+//! assert_eq!(session.username, "user123");
+//! ```
+
+use crate::{
+    compression, Algorithm, Clock, Encoder, Error, HmacSigner, SystemClock,
+    DEFAULT_MAX_DECOMPRESSED_SIZE,
+};
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::sync::Arc;
+use zeroize::Zeroizing;
+
+/// The default salt of ItsDangerous' `Signer` and `TimestampSigner`.
+pub const SIGNER_SALT: &[u8] = b"itsdangerous.Signer";
+
+/// The default salt of ItsDangerous' serialisers.
+pub const SERIALIZER_SALT: &[u8] = b"itsdangerous";
+
+/// The salt used by Flask to sign its session cookies.
+pub const FLASK_SESSION_SALT: &[u8] = b"cookie-session";
+
+/// The default separator of ItsDangerous.
+pub const DEFAULT_SEPARATOR: &str = ".";
+
+const ENGINE: general_purpose::GeneralPurpose = general_purpose::URL_SAFE_NO_PAD;
+
+/// An enum for defining how the signing key is derived from the secret key and the salt.
+#[derive(Default, Debug, Clone)]
+pub enum KeyDerivation {
+    // hash(salt + secret_key)
+    Concat,
+
+    #[default]
+    // hash(salt + "signer" + secret_key)
+    DjangoConcat,
+
+    // hmac(secret_key, salt)
+    Hmac,
+
+    // the secret key is used as is
+    None,
+}
+
+/// A signer that produces and verifies tokens in the same format as ItsDangerous' `Signer`.
+///
+/// The token has the structure of `<value><sep><signature>`.
+#[derive(Debug, Clone)]
+pub struct Signer {
+    signer: HmacSigner,
+    sep: String,
+}
+
+impl Signer {
+    /// Initialises the signer with the same parameters as ItsDangerous' `Signer`.
+    ///
+    /// ItsDangerous' defaults are [`SIGNER_SALT`], `KeyDerivation::DjangoConcat`, `Algorithm::SHA1` and [`DEFAULT_SEPARATOR`].
+    ///
+    /// An `EmptyKey` error is returned if the secret key is empty and
+    /// an `InvalidSeparator` error is returned if the separator contains any URL-safe base64 characters.
+    pub fn new(
+        secret_key: &[u8],
+        salt: &[u8],
+        key_derivation: KeyDerivation,
+        algo: Algorithm,
+        sep: &str,
+    ) -> Result<Self, Error> {
+        if !Encoder::UrlSafeNoPadding.is_valid_separator(sep) {
+            return Err(Error::InvalidSeparator(sep.to_string()));
+        }
+        if secret_key.is_empty() {
+            return Err(Error::EmptyKey);
+        }
+
+        let derived_key = Zeroizing::new(match key_derivation {
+            KeyDerivation::Concat => algo.digest(&[salt, secret_key].concat()),
+            KeyDerivation::DjangoConcat => algo.digest(&[salt, b"signer", secret_key].concat()),
+            KeyDerivation::Hmac => {
+                HmacSigner::from_expanded_key(secret_key, algo.clone(), Encoder::UrlSafeNoPadding)
+                    .sign_payload(salt)
+            }
+            KeyDerivation::None => secret_key.to_vec(),
+        });
+        Ok(Self {
+            signer: HmacSigner::from_expanded_key(&derived_key, algo, Encoder::UrlSafeNoPadding),
+            sep: sep.to_string(),
+        })
+    }
+
+    /// Signs the value and returns the token.
+    pub fn sign(&self, value: &str) -> String {
+        let signature = ENGINE.encode(self.signer.sign_payload(value.as_bytes()));
+        format!("{}{}{}", value, self.sep, signature)
+    }
+
+    /// Verifies the token and returns the original value.
+    ///
+    /// If the token does not contain the separator, an `InvalidInput` error is returned.
+    /// If the signature cannot be decoded, an `InvalidSignature` error is returned.
+    /// If the signature does not match, an `InvalidToken` error is returned.
+    pub fn unsign<'a>(&self, signed_value: &'a str) -> Result<&'a str, Error> {
+        let (value, signature) = signed_value
+            .rsplit_once(self.sep.as_str())
+            .ok_or_else(|| Error::InvalidInput(signed_value.to_string()))?;
+
+        let signature = ENGINE
+            .decode(signature)
+            .map_err(|_| Error::InvalidSignature)?;
+        if !self.signer.verify(value.as_bytes(), &signature) {
+            return Err(Error::InvalidToken);
+        }
+        Ok(value)
+    }
+}
+
+/// A signer that produces and verifies tokens in the same format as ItsDangerous' `TimestampSigner`.
+///
+/// The token has the structure of `<value><sep><timestamp><sep><signature>`
+/// where the timestamp is the base64 encoded big-endian Unix time in seconds.
+#[derive(Debug, Clone)]
+pub struct TimestampSigner {
+    signer: Signer,
+
+    // Source of the current time when signing and checking the age of a token
+    clock: Arc<dyn Clock>,
+}
+
+impl TimestampSigner {
+    pub fn new(signer: Signer) -> Self {
+        Self {
+            signer,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Sets the clock used to get the current time when signing and checking the age of a token,
+    /// which is the [`SystemClock`] by default.
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Signs the value with the current time of the signer's clock and returns the token.
+    pub fn sign(&self, value: &str) -> String {
+        self.sign_at(value, self.clock.now())
+    }
+
+    /// Signs the value with the provided timestamp instead of the current time.
+    pub fn sign_at(&self, value: &str, timestamp: chrono::DateTime<chrono::Utc>) -> String {
+        let timestamp = timestamp.timestamp().max(0).to_be_bytes();
+        let leading_zeros = timestamp.iter().take_while(|&&b| b == 0).count();
+        let timestamp = ENGINE.encode(&timestamp[leading_zeros..]);
+        self.signer
+            .sign(&format!("{}{}{}", value, self.signer.sep, timestamp))
+    }
+
+    /// Verifies the token and returns the original value along with the time it was signed at.
+    ///
+    /// The age of the token is not checked. On top of the errors returned by [`Signer::unsign`],
+    /// an `InvalidToken` error is returned if the embedded timestamp is missing or malformed.
+    pub fn unsign_with_timestamp<'a>(
+        &self,
+        signed_value: &'a str,
+    ) -> Result<(&'a str, chrono::DateTime<chrono::Utc>), Error> {
+        let value = self.signer.unsign(signed_value)?;
+        let (value, timestamp) = value
+            .rsplit_once(self.signer.sep.as_str())
+            .ok_or(Error::InvalidToken)?;
+
+        let timestamp = ENGINE
+            .decode(timestamp)
+            .ok()
+            .filter(|timestamp| timestamp.len() <= 8)
+            .map(|timestamp| {
+                timestamp
+                    .iter()
+                    .fold(0i64, |acc, &b| (acc << 8) | i64::from(b))
+            })
+            .and_then(|timestamp| chrono::DateTime::from_timestamp(timestamp, 0))
+            .ok_or(Error::InvalidToken)?;
+        Ok((value, timestamp))
+    }
+
+    /// Verifies the token and returns the original value.
+    ///
+    /// If `max_age` is provided, a `TokenExpired` error is returned if the token is older than `max_age`
+    /// or, like ItsDangerous, if the token was signed in the future.
+    pub fn unsign<'a>(
+        &self,
+        signed_value: &'a str,
+        max_age: Option<chrono::Duration>,
+    ) -> Result<&'a str, Error> {
+        let (value, timestamp) = self.unsign_with_timestamp(signed_value)?;
+        self.check_age(timestamp, max_age)?;
+        Ok(value)
+    }
+
+    /// Returns a `TokenExpired` error if the token is older than `max_age` or was signed in the future.
+    fn check_age(
+        &self,
+        timestamp: chrono::DateTime<chrono::Utc>,
+        max_age: Option<chrono::Duration>,
+    ) -> Result<(), Error> {
+        if let Some(max_age) = max_age {
+            let age = self.clock.now() - timestamp;
+            if age > max_age || age < chrono::Duration::zero() {
+                return Err(Error::TokenExpired);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A JSON formatter reproducing the separators of Python's `json.dumps()`.
+struct PythonFormatter {
+    // `","` and `":"` instead of `", "` and `": "`
+    compact: bool,
+}
+
+impl serde_json::ser::Formatter for PythonFormatter {
+    fn begin_array_value<W: ?Sized + std::io::Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> std::io::Result<()> {
+        match (first, self.compact) {
+            (true, _) => Ok(()),
+            (false, true) => writer.write_all(b","),
+            (false, false) => writer.write_all(b", "),
+        }
+    }
+
+    fn begin_object_key<W: ?Sized + std::io::Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> std::io::Result<()> {
+        self.begin_array_value(writer, first)
+    }
+
+    fn begin_object_value<W: ?Sized + std::io::Write>(
+        &mut self,
+        writer: &mut W,
+    ) -> std::io::Result<()> {
+        writer.write_all(if self.compact { b":" } else { b": " })
+    }
+}
+
+/// The options passed to Python's `json.dumps()` by the different serialisers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum JsonStyle {
+    // the default options used by ItsDangerous' `Serializer`
+    // which adds whitespace after the separators and escapes any non-ASCII characters
+    Default,
+
+    // `separators=(",", ":"), ensure_ascii=False` used by ItsDangerous' URL-safe serialisers
+    Compact,
+
+    // `separators=(",", ":"), sort_keys=True` used by Flask's session cookies
+    Flask,
+}
+
+/// Serialises the payload into JSON like Python's `json.dumps()`.
+fn dump_json<T: Serialize>(payload: &T, style: JsonStyle) -> Result<String, Error> {
+    let mut json = Vec::new();
+    let mut serializer = serde_json::Serializer::with_formatter(
+        &mut json,
+        PythonFormatter {
+            compact: style != JsonStyle::Default,
+        },
+    );
+    match style {
+        // serde_json's map is sorted by its keys
+        JsonStyle::Flask => {
+            serde_json::to_value(payload).and_then(|payload| payload.serialize(&mut serializer))
+        }
+        _ => payload.serialize(&mut serializer),
+    }
+    .map_err(|e| Error::SerialisationError(e.to_string()))?;
+
+    // serde_json only writes valid UTF-8
+    let json = String::from_utf8(json).expect("JSON should be valid utf-8");
+    if style == JsonStyle::Compact {
+        return Ok(json);
+    }
+
+    let mut escaped = String::with_capacity(json.len());
+    for c in json.chars() {
+        // Python's `ensure_ascii` only leaves the printable ASCII characters as is,
+        // hence DEL is escaped as well (the remaining control characters are already escaped by serde_json)
+        if c < '\x7f' {
+            escaped.push(c);
+            continue;
+        }
+        let mut units = [0u16; 2];
+        for unit in c.encode_utf16(&mut units) {
+            let _ = write!(escaped, "\\u{:04x}", unit);
+        }
+    }
+    Ok(escaped)
+}
+
+/// Base64 encodes the JSON payload, zlib compressing it if it makes the payload shorter.
+fn dump_url_safe_payload<T: Serialize>(payload: &T, style: JsonStyle) -> Result<String, Error> {
+    let json = dump_json(payload, style)?.into_bytes();
+    Ok(match compression::compress(&json) {
+        Some(compressed) => format!("{}{}", compression::PREFIX, ENGINE.encode(compressed)),
+        None => ENGINE.encode(json),
+    })
+}
+
+/// Decodes the base64 payload, decompressing it if it starts with a `.`.
+///
+/// The decompressed payload is limited to [`DEFAULT_MAX_DECOMPRESSED_SIZE`] bytes.
+fn load_url_safe_payload<T: for<'de> Deserialize<'de>>(payload: &str) -> Result<T, Error> {
+    let (payload, decompress) = match payload.strip_prefix(compression::PREFIX) {
+        Some(payload) => (payload, true),
+        None => (payload, false),
+    };
+
+    let mut json = ENGINE
+        .decode(payload)
+        .map_err(|_| Error::PayloadDecodeError)?;
+    if decompress {
+        json = compression::decompress(&json, DEFAULT_MAX_DECOMPRESSED_SIZE)?;
+    }
+    serde_json::from_slice(&json).map_err(|_| Error::InvalidPayload)
+}
+
+/// A serialiser that produces and verifies tokens in the same format as ItsDangerous' `Serializer`.
+///
+/// The payload is signed as compact JSON without being base64 encoded.
+#[derive(Debug, Clone)]
+pub struct Serializer {
+    signer: Signer,
+}
+
+impl Serializer {
+    pub fn new(signer: Signer) -> Self {
+        Self { signer }
+    }
+
+    /// Serialises the payload into JSON and signs it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the payload cannot be serialised, see [`Serializer::try_dumps`] for a non-panicking alternative.
+    pub fn dumps<T: Serialize>(&self, payload: &T) -> String {
+        self.try_dumps(payload).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Serialises the payload into JSON and signs it, returning a `SerialisationError` if it cannot be serialised.
+    pub fn try_dumps<T: Serialize>(&self, payload: &T) -> Result<String, Error> {
+        Ok(self.signer.sign(&dump_json(payload, JsonStyle::Default)?))
+    }
+
+    /// Verifies the token and returns the de-serialised payload.
+    pub fn loads<T: for<'de> Deserialize<'de>>(&self, token: &str) -> Result<T, Error> {
+        let payload = self.signer.unsign(token)?;
+        serde_json::from_str(payload).map_err(|_| Error::InvalidPayload)
+    }
+}
+
+/// A serialiser that produces and verifies tokens in the same format as ItsDangerous' `URLSafeSerializer`.
+///
+/// The JSON payload is base64 encoded and zlib compressed if it makes the payload shorter,
+/// in which case the payload is prefixed with a `.`.
+#[derive(Debug, Clone)]
+pub struct UrlSafeSerializer {
+    signer: Signer,
+}
+
+impl UrlSafeSerializer {
+    pub fn new(signer: Signer) -> Self {
+        Self { signer }
+    }
+
+    /// Serialises the payload into URL-safe base64 encoded JSON and signs it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the payload cannot be serialised, see [`UrlSafeSerializer::try_dumps`] for a non-panicking alternative.
+    pub fn dumps<T: Serialize>(&self, payload: &T) -> String {
+        self.try_dumps(payload).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Serialises the payload into URL-safe base64 encoded JSON and signs it,
+    /// returning a `SerialisationError` if it cannot be serialised.
+    pub fn try_dumps<T: Serialize>(&self, payload: &T) -> Result<String, Error> {
+        Ok(self
+            .signer
+            .sign(&dump_url_safe_payload(payload, JsonStyle::Compact)?))
+    }
+
+    /// Verifies the token and returns the de-serialised payload.
+    pub fn loads<T: for<'de> Deserialize<'de>>(&self, token: &str) -> Result<T, Error> {
+        load_url_safe_payload(self.signer.unsign(token)?)
+    }
+}
+
+/// A serialiser that produces and verifies tokens in the same format as ItsDangerous' `URLSafeTimedSerializer`.
+///
+/// This is the serialiser used by Flask for its session cookies, see [`UrlSafeTimedSerializer::flask_session`].
+#[derive(Debug, Clone)]
+pub struct UrlSafeTimedSerializer {
+    signer: TimestampSigner,
+    style: JsonStyle,
+}
+
+impl UrlSafeTimedSerializer {
+    pub fn new(signer: TimestampSigner) -> Self {
+        Self {
+            signer,
+            style: JsonStyle::Compact,
+        }
+    }
+
+    /// Initialises the serialiser the same way Flask does to sign its session cookies.
+    ///
+    /// Like Flask, the JSON payload has its keys sorted and any non-ASCII characters escaped.
+    pub fn flask_session(secret_key: &[u8]) -> Result<Self, Error> {
+        let signer = Signer::new(
+            secret_key,
+            FLASK_SESSION_SALT,
+            KeyDerivation::Hmac,
+            Algorithm::SHA1,
+            DEFAULT_SEPARATOR,
+        )?;
+        Ok(Self {
+            signer: TimestampSigner::new(signer),
+            style: JsonStyle::Flask,
+        })
+    }
+
+    /// Sets the clock used to get the current time when signing and checking the age of a token,
+    /// see [`TimestampSigner::with_clock`].
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.signer = self.signer.with_clock(clock);
+        self
+    }
+
+    /// Serialises the payload into URL-safe base64 encoded JSON and signs it with the current time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the payload cannot be serialised, see [`UrlSafeTimedSerializer::try_dumps`] for a non-panicking alternative.
+    pub fn dumps<T: Serialize>(&self, payload: &T) -> String {
+        self.dumps_at(payload, self.signer.clock.now())
+    }
+
+    /// Serialises the payload and signs it with the provided timestamp instead of the current time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the payload cannot be serialised, see [`UrlSafeTimedSerializer::try_dumps_at`] for a non-panicking alternative.
+    pub fn dumps_at<T: Serialize>(
+        &self,
+        payload: &T,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    ) -> String {
+        self.try_dumps_at(payload, timestamp)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Serialises the payload and signs it with the current time,
+    /// returning a `SerialisationError` if it cannot be serialised.
+    pub fn try_dumps<T: Serialize>(&self, payload: &T) -> Result<String, Error> {
+        self.try_dumps_at(payload, self.signer.clock.now())
+    }
+
+    /// Serialises the payload and signs it with the provided timestamp,
+    /// returning a `SerialisationError` if it cannot be serialised.
+    pub fn try_dumps_at<T: Serialize>(
+        &self,
+        payload: &T,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    ) -> Result<String, Error> {
+        Ok(self
+            .signer
+            .sign_at(&dump_url_safe_payload(payload, self.style)?, timestamp))
+    }
+
+    /// Verifies the token and returns the de-serialised payload along with the time it was signed at.
+    pub fn loads_with_timestamp<T: for<'de> Deserialize<'de>>(
+        &self,
+        token: &str,
+    ) -> Result<(T, chrono::DateTime<chrono::Utc>), Error> {
+        let (payload, timestamp) = self.signer.unsign_with_timestamp(token)?;
+        Ok((load_url_safe_payload(payload)?, timestamp))
+    }
+
+    /// Verifies the token and returns the de-serialised payload.
+    ///
+    /// If `max_age` is provided, a `TokenExpired` error is returned if the token is older than `max_age`.
+    pub fn loads<T: for<'de> Deserialize<'de>>(
+        &self,
+        token: &str,
+        max_age: Option<chrono::Duration>,
+    ) -> Result<T, Error> {
+        let (payload, timestamp) = self.signer.unsign_with_timestamp(token)?;
+        self.signer.check_age(timestamp, max_age)?;
+        load_url_safe_payload(payload)
+    }
+}
@@ -0,0 +1,210 @@
+use crate::{Algorithm, Clock, Encoder, Error, HmacSigner, KeyInfo, Payload, SystemClock, DELIM};
+use base64::Engine as _;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A struct that holds multiple keys identified by a key ID (kid).
+///
+/// Unlike [`RotatingSigner`](crate::RotatingSigner) which tries every fallback key in order,
+/// the `Keyring` struct embeds the kid of the signing key into the token as `<kid>.<payload>.<signature>`,
+/// allowing the verifying key to be looked up directly regardless of how many keys there are.
+///
+/// The kid is covered by the signature so that it cannot be swapped for another kid in the keyring.
+///
+/// Sample Usage:
+/// ```rust
+/// use hmac_serialiser::{Algorithm, Encoder, KeyInfo, Keyring, Payload};
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Serialize, Deserialize, Debug)]
+/// struct UserData {
+///     username: String,
+/// }
+/// impl Payload for UserData {
+///     fn get_exp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+///         None
+///     }
+/// }
+///
+/// let mut keyring = Keyring::new(
+///     "2024-01",
+///     KeyInfo { key: b"old_secret_key".to_vec(), salt: vec![], info: vec![] },
+///     Algorithm::SHA256,
+///     Encoder::UrlSafeNoPadding,
+/// ).unwrap();
+/// let token = keyring.sign(&UserData { username: "user123".to_string() });
+///
+/// // rotate to a new key while still accepting tokens signed with the old key
+/// keyring
+///     .add_key("2024-02", KeyInfo { key: b"new_secret_key".to_vec(), salt: vec![], info: vec![] })
+///     .unwrap();
+/// keyring.set_current("2024-02").unwrap();
+///
+/// let (user, kid): (UserData, _) = keyring.unsign_with_kid(&token).unwrap();
+/// assert_eq!(kid, "2024-01");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Keyring {
+    current: String,
+    signers: HashMap<String, HmacSigner>,
+    algo: Algorithm,
+    encoder: Encoder,
+
+    // Source of the current time shared by every key's signer
+    clock: Arc<dyn Clock>,
+
+    // Tolerance for clock drift shared by every key's signer
+    leeway: chrono::Duration,
+}
+
+impl Keyring {
+    /// Initialises the keyring with the key that is used for signing.
+    ///
+    /// An `InvalidKeyId` error is returned if the kid is empty or contains
+    /// characters other than ASCII letters, digits, `-` and `_`.
+    pub fn new(
+        kid: &str,
+        key_info: KeyInfo,
+        algo: Algorithm,
+        encoder: Encoder,
+    ) -> Result<Self, Error> {
+        let mut keyring = Self {
+            current: kid.to_string(),
+            signers: HashMap::new(),
+            algo,
+            encoder,
+            clock: Arc::new(SystemClock),
+            leeway: chrono::Duration::zero(),
+        };
+        keyring.add_key(kid, key_info)?;
+        Ok(keyring)
+    }
+
+    /// Sets the clock used to get the current time when checking the expiration time of a token
+    /// for every key, including the keys added afterwards, see [`HmacSigner::with_clock`].
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Arc::new(clock);
+        for signer in self.signers.values_mut() {
+            signer.clock = self.clock.clone();
+        }
+        self
+    }
+
+    /// Sets the tolerance for clock drift when checking the expiration time of a token
+    /// for every key, including the keys added afterwards, see [`HmacSigner::with_leeway`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the leeway is negative or greater than [`MAX_LEEWAY`](crate::MAX_LEEWAY).
+    pub fn with_leeway(mut self, leeway: chrono::Duration) -> Self {
+        if let Err(e) = HmacSigner::validate_leeway(leeway) {
+            panic!("{}", e);
+        }
+        self.leeway = leeway;
+        for signer in self.signers.values_mut() {
+            signer.leeway = leeway;
+        }
+        self
+    }
+
+    /// Adds a key that can be used to verify tokens, replacing any existing key with the same kid.
+    ///
+    /// The key is validated in the same way as [`HmacSigner::try_new`].
+    pub fn add_key(&mut self, kid: &str, key_info: KeyInfo) -> Result<(), Error> {
+        let is_valid = !kid.is_empty()
+            && kid
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !is_valid {
+            return Err(Error::InvalidKeyId(kid.to_string()));
+        }
+
+        let mut signer = HmacSigner::try_new(key_info, self.algo.clone(), self.encoder.clone())?;
+        signer.clock = self.clock.clone();
+        signer.leeway = self.leeway;
+        self.signers.insert(kid.to_string(), signer);
+        Ok(())
+    }
+
+    /// Removes a key so that tokens signed with it are no longer accepted.
+    ///
+    /// The current signing key cannot be removed, in which case `false` is returned.
+    pub fn remove_key(&mut self, kid: &str) -> bool {
+        kid != self.current && self.signers.remove(kid).is_some()
+    }
+
+    /// Sets the key used for signing new tokens.
+    ///
+    /// An `UnknownKeyId` error is returned if the kid has not been added to the keyring.
+    pub fn set_current(&mut self, kid: &str) -> Result<(), Error> {
+        if !self.signers.contains_key(kid) {
+            return Err(Error::UnknownKeyId(kid.to_string()));
+        }
+        self.current = kid.to_string();
+        Ok(())
+    }
+
+    /// Returns the kid of the key used for signing new tokens.
+    pub fn current(&self) -> &str {
+        &self.current
+    }
+
+    /// Signs the payload with the current key and returns the token which can be sent to the client.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the payload cannot be serialised, see [`Keyring::try_sign`] for a non-panicking alternative.
+    pub fn sign<T: Serialize + Payload>(&self, payload: &T) -> String {
+        self.try_sign(payload).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Signs the payload with the current key and returns the token,
+    /// or a `SerialisationError` if the payload cannot be serialised.
+    pub fn try_sign<T: Serialize + Payload>(&self, payload: &T) -> Result<String, Error> {
+        let signer = &self.signers[&self.current];
+        let token =
+            serde_json::to_vec(payload).map_err(|e| Error::SerialisationError(e.to_string()))?;
+        let token = signer.encoder.encode(&token);
+
+        let data = format!("{}{}{}", self.current, DELIM, token);
+        let signature = signer.sign_payload(data.as_bytes());
+        let signature = signer.encoder.encode(&signature);
+        Ok(format!("{}{}{}", data, DELIM, signature))
+    }
+
+    /// Verifies the token using the key identified by its kid and
+    /// returns the deserialised payload along with the kid.
+    ///
+    /// If the token does not contain three parts, an `InvalidInput` error is returned.
+    /// If the kid is not in the keyring, an `UnknownKeyId` error is returned.
+    /// The other errors are the same as the ones returned by [`HmacSigner::unsign`].
+    pub fn unsign_with_kid<'a, T: for<'de> Deserialize<'de> + Payload>(
+        &self,
+        token: &'a str,
+    ) -> Result<(T, &'a str), Error> {
+        let (kid, rest) = token
+            .split_once(DELIM)
+            .ok_or_else(|| Error::InvalidInput(token.to_string()))?;
+        if rest.split(DELIM).count() != 2 {
+            return Err(Error::InvalidInput(token.to_string()));
+        }
+
+        let signer = self
+            .signers
+            .get(kid)
+            .ok_or_else(|| Error::UnknownKeyId(kid.to_string()))?;
+        let (encoded_payload, signature) = signer.split_token(rest)?;
+
+        let data = &token[..kid.len() + DELIM.len_utf8() + encoded_payload.len()];
+        if !signer.verify(data.as_bytes(), &signature) {
+            return Err(Error::InvalidToken);
+        }
+        Ok((signer.load_payload(encoded_payload)?, kid))
+    }
+
+    /// Verifies the token using the key identified by its kid and returns the deserialised payload.
+    pub fn unsign<T: for<'de> Deserialize<'de> + Payload>(&self, token: &str) -> Result<T, Error> {
+        self.unsign_with_kid(token).map(|(payload, _)| payload)
+    }
+}
@@ -87,9 +87,85 @@
 //! Therefore, it is still considered secure to use HMAC-SHA1 to verify the authenticity of a given payload.
 //! However, it is still recommended to choose a stronger hash function like SHA256 or even SHA512.
 //!
+//! ## Builder
+//!
+//! `HmacSigner::builder()` configures the key, salt, info, algorithm, encoder, separator, leeway,
+//! payload format and compression fluently, validating all of them at once in `build()`.
+//!
+//! ## Clocks
+//!
+//! The expiration time of a token is checked against the signer's `Clock`, which is the `SystemClock` by default.
+//! `with_clock` can instead use a trusted time source or a `FixedClock` for deterministic tests,
+//! while `HmacSigner::unsign_at` checks a token against an explicit time.
+//!
+//! ## Leeway
+//!
+//! `with_leeway` accepts tokens for a short while after their expiration time or before their
+//! not-before and issued-at times to tolerate clock drift between the servers issuing and verifying them.
+//! The leeway must be between zero and `MAX_LEEWAY`, which is one day.
+//!
+//! ## Timed Tokens
+//!
+//! `TimedSigner` embeds the time the payload was signed into the token as `<payload>.<timestamp>.<signature>`.
+//! The maximum age is then supplied when verifying the token via `unsign_with_max_age`,
+//! removing the need for the payload to carry its own expiry field.
+//!
+//! ## Key Rotation
+//!
+//! `RotatingSigner` signs with the current key but also accepts tokens signed by an ordered list of
+//! previous keys, reporting which key matched so that tokens signed with an old key can be re-issued.
+//!
+//! `Keyring` instead embeds a key ID (kid) into the token as `<kid>.<payload>.<signature>`,
+//! allowing the verifying key to be looked up directly when there are many keys.
+//!
+//! ## Encrypted Tokens
+//!
+//! Tokens produced by `HmacSigner` are signed but not encrypted, so anyone can read the payload by base64 decoding it.
+//! `EncryptedSigner` encrypts the payload with ChaCha20-Poly1305 before signing it, deriving separate
+//! encryption and MAC keys from the same `KeyInfo`, so that the payload stays confidential.
+//!
+//! ## Compression
+//!
+//! `HmacSigner::with_compression` opts into compressing large payloads with deflate before they are encoded,
+//! using the same leading `.` convention as ItsDangerous, with the decompressed size bounded to prevent zip bombs.
+//!
+//! ## Payload Formats
+//!
+//! Payloads are serialised as JSON by default. `HmacSigner::with_format` can instead use CBOR, MessagePack,
+//! Postcard or Bincode, enabled via the `cbor`, `msgpack`, `postcard` and `bincode` features respectively,
+//! to produce shorter tokens without changing the `Payload` types.
+//!
+//! ## C# Interoperability
+//!
+//! The `interop` module provides `CSharpSigner` and `CSharpTimedSigner` which reproduce the wire format
+//! of the C# [HMACSerialiser](https://www.nuget.org/packages/HMACSerialiser) library byte for byte,
+//! allowing tokens to be signed in one language and verified in the other.
+//!
+//! ## ItsDangerous Compatibility
+//!
+//! The `itsdangerous` module can sign and verify tokens produced by Python's ItsDangerous library,
+//! including Flask session cookies, using the same secret key and salt.
+//!
+//! ## Web Frameworks
+//!
+//! The `axum` feature provides a `Signed<T>` extractor and a `require_token` middleware which read the token
+//! from the `Authorization` header, a cookie or the query string as configured by `web::TokenVerifier`,
+//! and turn the `Error` into a `401 Unauthorized` or `400 Bad Request` response.
+//! The `actix` feature provides the same `Signed<T>` extractor and `require_token` middleware for Actix Web.
+//! The `tower` feature provides a framework independent `TokenLayer` for axum, tonic and hyper services.
+//!
+//! ## Signed Cookies
+//!
+//! The `cookie` feature provides `CookieSigner` and `SignedCookieJar` which sign payloads into `HttpOnly`,
+//! `Secure` and `SameSite=Lax` cookies whose `Max-Age` and `Expires` attributes match the payload's expiration time.
+//!
+//! The `session` module builds cookie-backed sessions on top of them, consulting a `RevocationStore`
+//! when verifying a session so that "log out everywhere" invalidates all of a user's outstanding session cookies.
+//!
 //! ## Traits
 //!
-//! - `Payload`: A trait for data structures that can be signed and verified.
+//! - `Payload`: A trait for data structures that can be signed and verified,
+//!   exposing their expiration time and optionally their not-before and issued-at times.
 //!
 //! ## Errors
 //!
@@ -102,6 +178,25 @@
 //! - `HkdfExpandError`: Error during key expansion.
 //! - `HkdfFillError`: Error during key filling.
 //! - `TokenExpired`: Token has expired.
+//! - `SerialisationError`: The payload could not be serialised when signing it.
+//! - `EncryptionError`: The payload could not be encrypted.
+//! - `DecryptionError`: The authenticated payload could not be decrypted.
+//! - `PayloadTooLarge`: The decompressed payload exceeds the maximum size.
+//! - `IoError`: The reader failed when signing or verifying a stream.
+//! - `PayloadDecodeError`: The authenticated payload is not valid base64.
+//! - `PayloadUtf8Error`: The authenticated payload is not valid UTF-8.
+//! - `InvalidSeparator`: The separator contains characters from the encoder's base64 alphabet.
+//! - `EmptyKey`: The secret key is empty.
+//! - `KeyTooShort`: The secret key is shorter than the required minimum length.
+//! - `WeakKey`: The secret key consists of a single repeated byte.
+//! - `InvalidKeyId`: The key ID contains characters other than ASCII letters, digits, `-` and `_`.
+//! - `UnknownKeyId`: The key ID is not in the keyring.
+//! - `TokenNotYetValid`: The token's not-before time has not been reached yet.
+//! - `TokenIssuedInFuture`: The token's issued-at time is later than the current time.
+//! - `MissingToken`: The request does not contain a token.
+//! - `CookieTooLarge`: The signed cookie exceeds the maximum size.
+//! - `SessionRevoked`: The session was issued before the user's sessions were revoked.
+//! - `RevocationStoreError`: The revocation store could not be accessed.
 //!
 //! ## Contributing
 //!
@@ -109,15 +204,54 @@
 //!
 //! ```
 
+#[cfg(feature = "actix")]
+pub mod actix;
 pub mod algorithm;
+#[cfg(feature = "axum")]
+pub mod axum;
+pub mod builder;
+pub mod clock;
+#[cfg(feature = "cookie")]
+pub mod cookie;
+pub mod encrypted;
 pub mod errors;
 pub mod hkdf;
+pub mod interop;
+pub mod itsdangerous;
+pub mod keyring;
+pub mod payload_format;
+pub mod rotation;
+#[cfg(feature = "cookie")]
+pub mod session;
+pub mod stream;
+pub mod timed;
+#[cfg(feature = "tower")]
+pub mod tower;
+#[cfg(any(feature = "axum", feature = "actix", feature = "tower"))]
+pub mod web;
+
+mod compression;
+
+// compiles and runs the README's snippets as doc tests
+#[cfg(doctest)]
+#[doc = include_str!("../README.md")]
+struct ReadmeDoctests;
 
 use base64::{engine::general_purpose, Engine as _};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
 pub use algorithm::Algorithm;
+pub use builder::HmacSignerBuilder;
+pub use clock::{Clock, FixedClock, SystemClock};
+pub use encrypted::EncryptedSigner;
 pub use errors::Error;
+pub use keyring::Keyring;
+pub use payload_format::PayloadFormat;
+pub use rotation::{MatchedKey, RotatingSigner};
+pub use stream::HmacStream;
+pub use timed::TimedSigner;
 
 #[cfg(not(feature = "ring"))]
 use hmac::Mac;
@@ -127,6 +261,16 @@ use ring::hmac;
 
 pub const DELIM: char = '.';
 
+// fed into the MAC before a detached payload so that a detached signature can never be a token's signature,
+// as tokens only ever sign base64 encoded payloads and separators which cannot contain a NUL byte
+pub(crate) const DETACHED_PREFIX: &[u8] = b"\0hmac-serialiser.detached\0";
+
+/// The default maximum size in bytes of a decompressed payload, see [`HmacSigner::with_compression`].
+pub const DEFAULT_MAX_DECOMPRESSED_SIZE: usize = 1024 * 1024;
+
+/// The maximum leeway allowed by [`HmacSigner::with_leeway`], as clock drift beyond a day indicates a misconfiguration.
+pub const MAX_LEEWAY: chrono::Duration = chrono::Duration::days(1);
+
 /// An enum for defining the encoding scheme for the payload and the signature.
 ///
 /// Usually, you should use the encoder with no padding to shorten the token length by a few characters.
@@ -153,6 +297,21 @@ pub enum Encoder {
 }
 
 impl Encoder {
+    /// Checks whether the separator can be used alongside this encoder.
+    ///
+    /// Like the C# library, a separator is rejected if it is empty or contains any letters, digits,
+    /// or symbols from the encoder's base64 alphabet (including the padding character).
+    pub fn is_valid_separator(&self, sep: &str) -> bool {
+        let symbols: &[char] = match self {
+            Encoder::Standard | Encoder::StandardNoPadding => &['+', '/', '='],
+            Encoder::UrlSafe | Encoder::UrlSafeNoPadding => &['-', '_', '='],
+        };
+        !sep.is_empty()
+            && !sep
+                .chars()
+                .any(|c| c.is_alphanumeric() || symbols.contains(&c))
+    }
+
     #[inline]
     fn get_encoder(&self) -> general_purpose::GeneralPurpose {
         match self {
@@ -166,8 +325,9 @@ impl Encoder {
 
 /// A trait for custom payload types that can be signed and verified.
 ///
-/// This trait defines methods for retrieving expiration time and is used in conjunction with
-/// signing and verifying operations.
+/// This trait defines methods for retrieving the expiration, not-before and issued-at times
+/// and is used in conjunction with signing and verifying operations.
+/// Only `get_exp` has to be implemented as the other times are not checked by default.
 ///
 /// If your payload type does not require an expiration time, you can implement the trait as follows:
 /// ```rust
@@ -184,8 +344,41 @@ impl Encoder {
 ///   }
 /// }
 ///```
+///
+/// A token that only becomes valid at a scheduled time, such as a delayed invitation,
+/// can also provide a not-before time:
+/// ```rust
+/// use hmac_serialiser::Payload;
+/// use chrono::{DateTime, Utc};
+///
+/// struct Invitation {
+///    email: String,
+///    nbf: DateTime<Utc>,
+///    exp: DateTime<Utc>,
+/// }
+///
+/// impl Payload for Invitation {
+///    fn get_exp(&self) -> Option<DateTime<Utc>> {
+///       Some(self.exp)
+///    }
+///
+///    fn get_nbf(&self) -> Option<DateTime<Utc>> {
+///       Some(self.nbf)
+///    }
+/// }
+///```
 pub trait Payload {
     fn get_exp(&self) -> Option<chrono::DateTime<chrono::Utc>>;
+
+    /// Returns the time before which the token must not be accepted.
+    fn get_nbf(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        None
+    }
+
+    /// Returns the time at which the token was issued.
+    fn get_iat(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        None
+    }
 }
 
 /// A struct that holds the key information required for key expansion.
@@ -199,7 +392,13 @@ pub trait Payload {
 /// - `info` field is optional and can be used to provide application-specific information.
 ///
 /// The `salt` and the `info` fields can help to prevent key reuse and provide additional security.
-#[derive(Debug, Clone)]
+///
+/// The signers wipe the `KeyInfo` passed to them from memory once the key has been expanded,
+/// although the expanded key itself is only wiped with the RustCrypto backend, see [`HmacSigner`].
+/// Every `KeyInfo`, including its clones, is also wiped from memory when it is dropped,
+/// hence struct update syntax such as `..Default::default()` cannot be used and every field has to be set.
+/// Its `Debug` output only contains a fingerprint derived from the key so that it can be logged safely.
+#[derive(Clone, Default)]
 pub struct KeyInfo {
     // Main secret key
     pub key: Vec<u8>,
@@ -211,29 +410,89 @@ pub struct KeyInfo {
     pub info: Vec<u8>,
 }
 
-impl Default for KeyInfo {
-    fn default() -> Self {
-        Self {
-            key: vec![],
-            salt: vec![],
-            info: vec![],
-        }
+impl Zeroize for KeyInfo {
+    fn zeroize(&mut self) {
+        self.key.zeroize();
+        self.salt.zeroize();
+        self.info.zeroize();
+    }
+}
+
+impl Drop for KeyInfo {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for KeyInfo {}
+
+impl std::fmt::Debug for KeyInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyInfo")
+            .field("key_fingerprint", &key_fingerprint(&self.key))
+            .finish_non_exhaustive()
     }
 }
 
+// HKDF info used to derive the key fingerprints
+const FINGERPRINT_INFO: &[u8] = b"hmac-serialiser.key-fingerprint";
+
+/// Returns the first 4 bytes derived from the key via HKDF-SHA256 in hex,
+/// which identifies the key in logs without revealing it or a plain hash of it.
+pub(crate) fn key_fingerprint(key: &[u8]) -> String {
+    hkdf::HkdfWrapper::new(Algorithm::SHA256)
+        .expand_to_length(key, &[], FINGERPRINT_INFO, 4)
+        .expect("4 bytes should be a valid HKDF output length")
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
 /// A struct that holds the HMAC signer logic.
 ///
 /// The `HmacSigner` struct is used for signing and verifying the payload using HMAC signatures.
-#[derive(Debug, Clone)]
+///
+/// With the default RustCrypto backend, the expanded key is wiped from memory when the signer is dropped.
+/// With the `ring` feature, the expanded key is held in a `ring::hmac::Key` which ring does not wipe from memory.
+/// In both cases, its `Debug` output only contains a fingerprint of the expanded key.
+#[derive(Clone)]
 pub struct HmacSigner {
     #[cfg(not(feature = "ring"))]
-    expanded_key: Vec<u8>,
+    expanded_key: Zeroizing<Vec<u8>>,
     #[cfg(not(feature = "ring"))]
     algo: Algorithm,
     #[cfg(feature = "ring")]
     expanded_key: hmac::Key,
 
+    // Fingerprint of the expanded key for the Debug output
+    key_fingerprint: String,
+
     encoder: general_purpose::GeneralPurpose,
+
+    // Maximum decompressed payload size if compression is enabled
+    max_decompressed_size: Option<usize>,
+
+    // Serialisation format of the payload
+    format: PayloadFormat,
+
+    // Separator between the payload and the signature
+    sep: String,
+
+    // Tolerance for clock drift when checking the expiration time
+    leeway: chrono::Duration,
+
+    // Source of the current time when checking the expiration time
+    clock: Arc<dyn Clock>,
+}
+
+impl std::fmt::Debug for HmacSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HmacSigner")
+            .field("key_fingerprint", &self.key_fingerprint)
+            .field("format", &self.format)
+            .field("sep", &self.sep)
+            .finish_non_exhaustive()
+    }
 }
 
 #[cfg(not(feature = "ring"))]
@@ -263,33 +522,326 @@ macro_rules! hmac_verify {
 }
 
 impl HmacSigner {
+    /// Initialises the signer, panicking if the key is invalid.
+    ///
+    /// This is a convenience wrapper around [`HmacSigner::try_new`] for keys that are known to be valid at compile time.
+    /// Prefer [`HmacSigner::try_new`] when the key is loaded at runtime, such as from a config file or an environment variable.
     pub fn new(key_info: KeyInfo, algo: Algorithm, encoder: Encoder) -> Self {
-        if key_info.key.is_empty() {
-            panic!("Key cannot be empty"); // panic if key is empty as it is usually due to developer error
+        // panic if the key is invalid as it is usually due to developer error
+        Self::try_new(key_info, algo, encoder).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Initialises the signer, returning an error if the key is invalid.
+    ///
+    /// An `EmptyKey` error is returned if the key is empty and a `WeakKey` error is returned
+    /// if the key consists of a single repeated byte such as `aaaaaaaa`.
+    ///
+    /// Sample Usage:
+    /// ```rust
+    /// use hmac_serialiser::{Algorithm, Encoder, Error, HmacSigner, KeyInfo};
+    ///
+    /// let key_info = KeyInfo {
+    ///     key: vec![],
+    ///     salt: vec![],
+    ///     info: vec![],
+    /// };
+    /// let result = HmacSigner::try_new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+    /// assert_eq!(result.unwrap_err(), Error::EmptyKey);
+    /// ```
+    pub fn try_new(key_info: KeyInfo, algo: Algorithm, encoder: Encoder) -> Result<Self, Error> {
+        Self::try_new_with_min_key_length(key_info, 1, algo, encoder)
+    }
+
+    /// Initialises the signer like [`HmacSigner::try_new`] but also requires the key
+    /// to be at least `min_key_length` bytes long, otherwise a `KeyTooShort` error is returned.
+    pub fn try_new_with_min_key_length(
+        key_info: KeyInfo,
+        min_key_length: usize,
+        algo: Algorithm,
+        encoder: Encoder,
+    ) -> Result<Self, Error> {
+        let key_info = Zeroizing::new(key_info);
+        let key = &key_info.key;
+        if key.is_empty() {
+            return Err(Error::EmptyKey);
+        }
+        if key.len() < min_key_length {
+            return Err(Error::KeyTooShort(min_key_length));
+        }
+        if key.len() > 1 && key.iter().all(|&b| b == key[0]) {
+            return Err(Error::WeakKey);
         }
 
         let expanded_key = hkdf::HkdfWrapper::new(algo.clone()).expand(
             &key_info.key,
             &key_info.salt,
             &key_info.info,
-        );
+        )?;
+        Ok(Self::from_expanded_key(&expanded_key, algo, encoder))
+    }
+
+    /// Initialises the signer like [`HmacSigner::try_new`] but separates the payload
+    /// and the signature with `sep` instead of [`DELIM`].
+    ///
+    /// Like the C# library, an `InvalidSeparator` error is returned if the separator is empty
+    /// or contains any characters from the encoder's base64 alphabet.
+    ///
+    /// Sample Usage:
+    /// ```rust
+    /// use hmac_serialiser::{Algorithm, Encoder, HmacSigner, KeyInfo};
+    ///
+    /// let key_info = KeyInfo {
+    ///     key: b"your_secret_key".to_vec(),
+    ///     salt: vec![],
+    ///     info: vec![],
+    /// };
+    /// let signer = HmacSigner::try_new_with_separator(
+    ///     key_info,
+    ///     Algorithm::SHA256,
+    ///     Encoder::UrlSafeNoPadding,
+    ///     "~",
+    /// ).unwrap();
+    /// let token = signer.sign_str("user123");
+    /// assert!(token.contains('~'));
+    /// ```
+    pub fn try_new_with_separator(
+        key_info: KeyInfo,
+        algo: Algorithm,
+        encoder: Encoder,
+        sep: &str,
+    ) -> Result<Self, Error> {
+        if !encoder.is_valid_separator(sep) {
+            return Err(Error::InvalidSeparator(sep.to_string()));
+        }
+        let mut signer = Self::try_new(key_info, algo, encoder)?;
+        signer.sep = sep.to_string();
+        Ok(signer)
+    }
 
+    /// Initialises the signer with a key that has already been expanded via HKDF.
+    pub(crate) fn from_expanded_key(
+        expanded_key: &[u8],
+        algo: Algorithm,
+        encoder: Encoder,
+    ) -> Self {
         #[cfg(feature = "ring")]
         {
-            let expanded_key = hmac::Key::new(algo.to_hmac(), &expanded_key);
-            return Self {
-                expanded_key,
+            Self {
+                key_fingerprint: key_fingerprint(expanded_key),
+                expanded_key: hmac::Key::new(algo.to_hmac(), expanded_key),
                 encoder: encoder.get_encoder(),
-            };
+                max_decompressed_size: None,
+                format: PayloadFormat::default(),
+                sep: DELIM.to_string(),
+                leeway: chrono::Duration::zero(),
+                clock: Arc::new(SystemClock),
+            }
         }
         #[cfg(not(feature = "ring"))]
         Self {
-            expanded_key,
+            key_fingerprint: key_fingerprint(expanded_key),
+            expanded_key: Zeroizing::new(expanded_key.to_vec()),
             algo,
             encoder: encoder.get_encoder(),
+            max_decompressed_size: None,
+            format: PayloadFormat::default(),
+            sep: DELIM.to_string(),
+            leeway: chrono::Duration::zero(),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Enables compressing the payload before it is base64 encoded.
+    ///
+    /// Like ItsDangerous, the payload is only compressed with deflate if it makes the token shorter,
+    /// in which case the encoded payload is prefixed with a `.` to mark it as compressed.
+    ///
+    /// As a small compressed payload can decompress into a very large one, decompression stops with a
+    /// `PayloadTooLarge` error once the payload exceeds `max_decompressed_size` bytes.
+    /// [`DEFAULT_MAX_DECOMPRESSED_SIZE`] can be used if you do not have a specific limit in mind.
+    ///
+    /// Note that compressed tokens can only be verified by a signer that has compression enabled.
+    ///
+    /// Sample Usage:
+    /// ```rust
+    /// use hmac_serialiser::{Algorithm, Encoder, HmacSigner, KeyInfo, DEFAULT_MAX_DECOMPRESSED_SIZE};
+    ///
+    /// let key_info = KeyInfo {
+    ///    key: b"your_secret_key".to_vec(),
+    ///    salt: vec![],
+    ///    info: vec![],
+    /// };
+    /// let signer = HmacSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding)
+    ///     .with_compression(DEFAULT_MAX_DECOMPRESSED_SIZE);
+    /// ```
+    pub fn with_compression(mut self, max_decompressed_size: usize) -> Self {
+        self.max_decompressed_size = Some(max_decompressed_size);
+        self
+    }
+
+    /// Sets the format used to serialise the payload, which is JSON by default.
+    ///
+    /// Tokens can only be verified by a signer that uses the same format.
+    ///
+    /// Sample Usage:
+    /// ```rust
+    /// use hmac_serialiser::{Algorithm, Encoder, HmacSigner, KeyInfo, PayloadFormat};
+    ///
+    /// let key_info = KeyInfo {
+    ///    key: b"your_secret_key".to_vec(),
+    ///    salt: vec![],
+    ///    info: vec![],
+    /// };
+    /// let signer = HmacSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding)
+    ///     .with_format(PayloadFormat::Json);
+    /// ```
+    pub fn with_format(mut self, format: PayloadFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Sets the clock used to get the current time when checking the expiration time of a token,
+    /// which is the [`SystemClock`] by default.
+    ///
+    /// See [`FixedClock`] for a sample usage.
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Sets how long a token is still accepted after its expiration time, or before its not-before
+    /// and issued-at times, to tolerate clock drift between the servers issuing and verifying tokens.
+    /// No leeway is given by default.
+    ///
+    /// Sample Usage:
+    /// ```rust
+    /// use hmac_serialiser::{Algorithm, Encoder, HmacSigner, KeyInfo};
+    ///
+    /// let key_info = KeyInfo {
+    ///    key: b"your_secret_key".to_vec(),
+    ///    salt: vec![],
+    ///    info: vec![],
+    /// };
+    /// let signer = HmacSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding)
+    ///     .with_leeway(chrono::Duration::seconds(5));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the leeway is negative or greater than [`MAX_LEEWAY`],
+    /// see [`HmacSignerBuilder::leeway`] for a non-panicking alternative.
+    pub fn with_leeway(mut self, leeway: chrono::Duration) -> Self {
+        if let Err(e) = Self::validate_leeway(leeway) {
+            panic!("{}", e);
+        }
+        self.leeway = leeway;
+        self
+    }
+
+    /// Returns an `InvalidInput` error if the leeway is negative or greater than [`MAX_LEEWAY`].
+    pub(crate) fn validate_leeway(leeway: chrono::Duration) -> Result<(), Error> {
+        if leeway < chrono::Duration::zero() {
+            return Err(Error::InvalidInput(
+                "leeway must not be negative".to_string(),
+            ));
+        }
+        if leeway > MAX_LEEWAY {
+            return Err(Error::InvalidInput(format!(
+                "leeway must not exceed {} seconds",
+                MAX_LEEWAY.num_seconds()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns the current time according to the signer's clock.
+    pub(crate) fn now(&self) -> chrono::DateTime<chrono::Utc> {
+        self.clock.now()
+    }
+
+    /// Checks the payload's expiration, not-before and issued-at times against `now`, allowing for the leeway.
+    ///
+    /// A time that overflows once the leeway is applied is past the range of `DateTime` and hence does not bound the token.
+    pub(crate) fn check_time_claims<T: Payload>(
+        &self,
+        payload: &T,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), Error> {
+        if let Some(expiry) = payload.get_exp() {
+            if expiry
+                .checked_add_signed(self.leeway)
+                .is_some_and(|expiry| expiry < now)
+            {
+                return Err(Error::TokenExpired);
+            }
+        }
+        if let Some(not_before) = payload.get_nbf() {
+            if not_before
+                .checked_sub_signed(self.leeway)
+                .is_some_and(|not_before| not_before > now)
+            {
+                return Err(Error::TokenNotYetValid);
+            }
+        }
+        if let Some(issued_at) = payload.get_iat() {
+            if issued_at
+                .checked_sub_signed(self.leeway)
+                .is_some_and(|issued_at| issued_at > now)
+            {
+                return Err(Error::TokenIssuedInFuture);
+            }
+        }
+        Ok(())
+    }
+
+    /// Base64 encodes the serialised payload, compressing it first if compression is enabled and makes it shorter.
+    pub(crate) fn encode_payload(&self, payload: &[u8]) -> String {
+        if self.max_decompressed_size.is_some() {
+            if let Some(compressed) = compression::compress(payload) {
+                return format!("{}{}", compression::PREFIX, self.encoder.encode(compressed));
+            }
+        }
+        self.encoder.encode(payload)
+    }
+
+    /// Decodes an encoded payload whose signature has already been verified into bytes.
+    ///
+    /// Even though the signature is valid, the payload may still fail to decode if it was
+    /// signed with a different encoder or by another tool that signs raw bytes.
+    pub(crate) fn decode_payload_bytes(&self, encoded_payload: &str) -> Result<Vec<u8>, Error> {
+        let compressed_payload = encoded_payload
+            .strip_prefix(compression::PREFIX)
+            .zip(self.max_decompressed_size);
+        match compressed_payload {
+            Some((encoded_payload, max_decompressed_size)) => {
+                let compressed = self
+                    .encoder
+                    .decode(encoded_payload)
+                    .map_err(|_| Error::PayloadDecodeError)?;
+                compression::decompress(&compressed, max_decompressed_size)
+            }
+            None => self
+                .encoder
+                .decode(encoded_payload)
+                .map_err(|_| Error::PayloadDecodeError),
         }
     }
 
+    /// Decodes an encoded payload whose signature has already been verified into a string.
+    pub(crate) fn decode_payload(&self, encoded_payload: &str) -> Result<String, Error> {
+        let decoded_payload = self.decode_payload_bytes(encoded_payload)?;
+        String::from_utf8(decoded_payload).map_err(|_| Error::PayloadUtf8Error)
+    }
+
+    /// Decodes and de-serialises an encoded payload whose signature has already been verified.
+    pub(crate) fn deserialise_payload<T: for<'de> Deserialize<'de>>(
+        &self,
+        encoded_payload: &str,
+    ) -> Result<T, Error> {
+        let payload = self.decode_payload_bytes(encoded_payload)?;
+        self.format.deserialise(&payload)
+    }
+
     #[inline]
     #[cfg(not(feature = "ring"))]
     fn sign_payload(&self, payload: &[u8]) -> Vec<u8> {
@@ -331,15 +883,18 @@ impl HmacSigner {
     /// Before verifying the payload, the input token is split into two parts: the encoded payload and the signature.
     /// If the token does not contain two parts, an `InvalidInput` error is returned.
     ///
-    /// Afterwards, if the encoded payload is empty, an `InvalidToken` error is returned even if the signature is valid.
-    ///
     /// The signature is then decoded using the provided encoder. If the decoding fails, an `InvalidSignature` error is returned.
     ///
     /// The encoded payload and the signature are then verified via HMAC. If the verification fails, an `InvalidToken` error is returned.
     ///
     /// If the encoded payload is valid, the payload is decoded and deserialised using serde.
+    /// An empty payload signed via [`HmacSigner::sign_bytes`] fails to deserialise with an `InvalidPayload` error.
+    /// If the payload is not valid base64 or UTF-8, a `PayloadDecodeError` or a `PayloadUtf8Error` error is returned respectively.
     /// If the payload's expiration time is not provided, the deserialized payload is returned.
-    /// Otherwise, the expiration time is checked against the current time. If the expiration time is earlier than the current time, a `TokenExpired` error is returned.
+    /// Otherwise, the expiration time is checked against the current time of the signer's clock.
+    /// If the expiration time plus the signer's leeway is earlier than the current time, a `TokenExpired` error is returned.
+    /// Likewise, a `TokenNotYetValid` or a `TokenIssuedInFuture` error is returned if the payload's not-before
+    /// or issued-at time minus the leeway is later than the current time.
     ///
     /// Sample Usage:
     /// ```rust
@@ -369,43 +924,133 @@ impl HmacSigner {
     /// let result = signer.unsign::<UserData>(&"token.signature");
     /// ```
     pub fn unsign<T: for<'de> Deserialize<'de> + Payload>(&self, token: &str) -> Result<T, Error> {
-        let parts: Vec<&str> = token.split(DELIM).collect();
-        if parts.len() != 2 {
-            return Err(Error::InvalidInput(token.to_string()));
-        }
+        self.unsign_at(token, self.now())
+    }
 
-        let encoded_payload = parts[0];
-        if encoded_payload.is_empty() {
+    /// Verifies the token like [`HmacSigner::unsign`] but checks its expiration time against `now`
+    /// instead of the current time of the signer's clock.
+    ///
+    /// Sample Usage:
+    /// ```rust
+    /// use hmac_serialiser::{Algorithm, Encoder, Error, HmacSigner, KeyInfo, Payload};
+    /// use serde::{Serialize, Deserialize};
+    ///
+    /// #[derive(Serialize, Deserialize, Debug)]
+    /// struct UserData {
+    ///     username: String,
+    ///     exp: chrono::DateTime<chrono::Utc>,
+    /// }
+    /// impl Payload for UserData {
+    ///     fn get_exp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+    ///         Some(self.exp)
+    ///     }
+    /// }
+    ///
+    /// let key_info = KeyInfo {
+    ///     key: b"your_secret_key".to_vec(),
+    ///     salt: vec![],
+    ///     info: vec![],
+    /// };
+    /// let signer = HmacSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+    ///
+    /// let exp = chrono::Utc::now() + chrono::Duration::hours(1);
+    /// let token = signer.sign(&UserData { username: "user123".to_string(), exp });
+    /// assert!(signer.unsign_at::<UserData>(&token, exp).is_ok());
+    ///
+    /// let result = signer.unsign_at::<UserData>(&token, exp + chrono::Duration::seconds(1));
+    /// assert_eq!(result.unwrap_err(), Error::TokenExpired);
+    /// ```
+    pub fn unsign_at<T: for<'de> Deserialize<'de> + Payload>(
+        &self,
+        token: &str,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<T, Error> {
+        let encoded_payload = self.verify_token(token)?;
+        self.load_payload_at(encoded_payload, now)
+    }
+
+    /// Verifies the token and returns the raw bytes signed via [`HmacSigner::sign_bytes`].
+    ///
+    /// The errors are the same as the ones returned by [`HmacSigner::unsign`]
+    /// except that the payload is not deserialised nor checked for its expiration time.
+    ///
+    /// Sample Usage:
+    /// ```rust
+    /// use hmac_serialiser::{HmacSigner, KeyInfo, Encoder, Algorithm};
+    ///
+    /// let key_info = KeyInfo {
+    ///    key: b"your_secret_key".to_vec(),
+    ///    salt: vec![],
+    ///    info: vec![],
+    /// };
+    /// let signer = HmacSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+    ///
+    /// let token = signer.sign_bytes(&[0, 1, 2, 3]);
+    /// assert_eq!(signer.unsign_bytes(&token).unwrap(), vec![0, 1, 2, 3]);
+    ///
+    /// let token = signer.sign_str("/uploads/user123/avatar.png");
+    /// assert_eq!(signer.unsign_str(&token).unwrap(), "/uploads/user123/avatar.png");
+    /// ```
+    pub fn unsign_bytes(&self, token: &str) -> Result<Vec<u8>, Error> {
+        let encoded_payload = self.verify_token(token)?;
+        self.decode_payload_bytes(encoded_payload)
+    }
+
+    /// Verifies the token and returns the string signed via [`HmacSigner::sign_str`].
+    ///
+    /// A `PayloadUtf8Error` is returned if the signed payload is not valid UTF-8.
+    /// The other errors are the same as the ones returned by [`HmacSigner::unsign_bytes`].
+    pub fn unsign_str(&self, token: &str) -> Result<String, Error> {
+        let encoded_payload = self.verify_token(token)?;
+        self.decode_payload(encoded_payload)
+    }
+
+    /// Verifies the token's signature and returns its encoded payload.
+    pub(crate) fn verify_token<'a>(&self, token: &'a str) -> Result<&'a str, Error> {
+        let (encoded_payload, signature) = self.split_token(token)?;
+        if !self.verify(encoded_payload.as_bytes(), &signature) {
             return Err(Error::InvalidToken);
         }
+        Ok(encoded_payload)
+    }
+
+    /// Splits the token into the encoded payload and the decoded signature.
+    pub(crate) fn split_token<'a>(&self, token: &'a str) -> Result<(&'a str, Vec<u8>), Error> {
+        let (encoded_payload, signature) = token
+            .rsplit_once(self.sep.as_str())
+            .ok_or_else(|| Error::InvalidInput(token.to_string()))?;
+
+        // the separator can only appear in the payload as part of the compression prefix
+        let payload = encoded_payload
+            .strip_prefix(compression::PREFIX)
+            .unwrap_or(encoded_payload);
+        if payload.contains(self.sep.as_str()) {
+            return Err(Error::InvalidInput(token.to_string()));
+        }
 
         let signature = self
             .encoder
-            .decode(parts[1])
+            .decode(signature)
             .map_err(|_| Error::InvalidSignature)?;
-        let encoded_payload = parts[0].as_bytes();
-        if !self.verify(&encoded_payload, &signature) {
-            return Err(Error::InvalidToken);
-        }
+        Ok((encoded_payload, signature))
+    }
 
-        // at this pt, the token is valid and hence we can safely unwrap
-        let decoded_payload = self
-            .encoder
-            .decode(encoded_payload)
-            .expect("payload should be valid base64");
-        let payload = String::from_utf8(decoded_payload).expect("payload should be valid utf-8");
-
-        // usually de-serialisation errors are
-        // caused when the developer was expecting the
-        // wrong payload type or has recently changed the payload type
-        let deserialised_payload: T =
-            serde_json::from_str(&payload).map_err(|_| Error::InvalidPayload)?;
-
-        if let Some(expiry) = deserialised_payload.get_exp() {
-            if expiry < chrono::Utc::now() {
-                return Err(Error::TokenExpired);
-            }
-        }
+    /// De-serialises the verified payload and checks its time claims against the signer's clock.
+    pub(crate) fn load_payload<T: for<'de> Deserialize<'de> + Payload>(
+        &self,
+        encoded_payload: &str,
+    ) -> Result<T, Error> {
+        self.load_payload_at(encoded_payload, self.now())
+    }
+
+    /// De-serialises the verified payload and checks its time claims against `now`.
+    pub(crate) fn load_payload_at<T: for<'de> Deserialize<'de> + Payload>(
+        &self,
+        encoded_payload: &str,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<T, Error> {
+        let deserialised_payload: T = self.deserialise_payload(encoded_payload)?;
+        self.check_time_claims(&deserialised_payload, now)?;
         Ok(deserialised_payload)
     }
 
@@ -437,11 +1082,102 @@ impl HmacSigner {
     /// let user = UserData { username: "user123".to_string() };
     /// let result: String = signer.sign(&user);
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the payload cannot be serialised, see [`HmacSigner::try_sign`] for a non-panicking alternative.
     pub fn sign<T: Serialize + Payload>(&self, payload: &T) -> String {
-        let token = serde_json::to_string(payload).unwrap();
-        let token = self.encoder.encode(token.as_bytes());
+        self.try_sign(payload).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Signs the payload and returns the token, or a `SerialisationError` if the payload cannot be serialised.
+    ///
+    /// Serialisation can fail if the payload contains a map with non-string keys when using JSON or
+    /// if the payload has a custom `Serialize` implementation that returns an error.
+    ///
+    /// Sample Usage:
+    /// ```rust
+    /// use hmac_serialiser::{HmacSigner, KeyInfo, Encoder, Algorithm, Error, Payload};
+    /// use serde::Serialize;
+    /// use std::collections::HashMap;
+    ///
+    /// #[derive(Serialize)]
+    /// struct Scores {
+    ///     scores: HashMap<(u32, u32), u32>,
+    /// }
+    /// impl Payload for Scores {
+    ///    fn get_exp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+    ///        None
+    ///    }
+    /// }
+    ///
+    /// let key_info = KeyInfo {
+    ///    key: b"your_secret_key".to_vec(),
+    ///    salt: vec![],
+    ///    info: vec![],
+    /// };
+    /// let signer = HmacSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafe);
+    /// let payload = Scores { scores: HashMap::from([((1, 2), 3)]) };
+    /// let result = signer.try_sign(&payload);
+    /// assert!(matches!(result, Err(Error::SerialisationError(_))));
+    /// ```
+    pub fn try_sign<T: Serialize + Payload>(&self, payload: &T) -> Result<String, Error> {
+        let payload = self.format.serialise(payload)?;
+        Ok(self.sign_bytes(&payload))
+    }
+
+    /// Signs the raw bytes as is, without serialising them, and returns the token.
+    ///
+    /// An empty payload produces a token of the form `<sep><signature>` which can be verified via [`HmacSigner::unsign_bytes`].
+    pub fn sign_bytes(&self, payload: &[u8]) -> String {
+        let token = self.encode_payload(payload);
         let signature = self.sign_payload(token.as_bytes());
         let signature = self.encoder.encode(&signature);
-        format!("{}{}{}", token, DELIM, signature)
+        format!("{}{}{}", token, self.sep, signature)
+    }
+
+    /// Signs the string as is, without wrapping it in JSON quotes, and returns the token.
+    pub fn sign_str(&self, payload: &str) -> String {
+        self.sign_bytes(payload.as_bytes())
+    }
+
+    /// Signs the payload and returns only the encoded signature, leaving the payload to be stored or sent separately.
+    ///
+    /// This is useful for attaching a signature to a webhook body or an uploaded file in a header.
+    /// Unlike [`HmacSigner::sign_bytes`], the payload is not compressed.
+    ///
+    /// The payload is signed under a different domain than tokens,
+    /// hence a detached signature cannot be appended to a payload to forge a token.
+    ///
+    /// Sample Usage:
+    /// ```rust
+    /// use hmac_serialiser::{HmacSigner, KeyInfo, Encoder, Algorithm, Error};
+    ///
+    /// let key_info = KeyInfo {
+    ///    key: b"your_secret_key".to_vec(),
+    ///    info: b"webhook".to_vec(),
+    ///    salt: vec![],
+    /// };
+    /// let signer = HmacSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+    ///
+    /// let body = br#"{"event":"payment.succeeded"}"#;
+    /// let signature = signer.sign_detached(body);
+    /// assert!(signer.verify_detached(body, &signature).is_ok());
+    /// assert_eq!(signer.verify_detached(b"tampered", &signature), Err(Error::InvalidToken));
+    /// ```
+    pub fn sign_detached(&self, payload: &[u8]) -> String {
+        let mut stream = self.stream();
+        stream.update(payload);
+        stream.finalize()
+    }
+
+    /// Verifies the signature produced by [`HmacSigner::sign_detached`] for the payload in constant time.
+    ///
+    /// If the signature cannot be decoded, an `InvalidSignature` error is returned.
+    /// If the signature does not match the payload, an `InvalidToken` error is returned.
+    pub fn verify_detached(&self, payload: &[u8], signature: &str) -> Result<(), Error> {
+        let mut stream = self.stream();
+        stream.update(payload);
+        stream.verify(signature)
     }
 }
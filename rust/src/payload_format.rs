@@ -0,0 +1,104 @@
+use crate::Error;
+use serde::{Deserialize, Serialize};
+
+/// An enum for defining the format used to serialise the payload before it is encoded and signed.
+///
+/// JSON is used by default. The binary formats are enabled via their respective features
+/// and usually produce much shorter tokens for payloads containing UUIDs, byte arrays or numeric IDs.
+///
+/// | Format        | Feature   |
+/// |---------------|-----------|
+/// | `Json`        | (default) |
+/// | `Cbor`        | `cbor`    |
+/// | `MessagePack` | `msgpack` |
+/// | `Postcard`    | `postcard`|
+/// | `Bincode`     | `bincode` |
+///
+/// Note: Postcard and Bincode are not self-describing, hence they do not support payloads
+/// that rely on `deserialize_any` such as `serde_json::Value` or untagged enums.
+///
+/// The enum is non-exhaustive as its variants depend on the enabled features,
+/// which may be enabled by another crate in the dependency graph.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PayloadFormat {
+    #[default]
+    // JSON via serde_json
+    Json,
+
+    // CBOR via ciborium
+    #[cfg(feature = "cbor")]
+    Cbor,
+
+    // MessagePack via rmp-serde with struct fields serialised as maps
+    #[cfg(feature = "msgpack")]
+    MessagePack,
+
+    // Postcard via postcard
+    #[cfg(feature = "postcard")]
+    Postcard,
+
+    // Bincode via bincode
+    #[cfg(feature = "bincode")]
+    Bincode,
+}
+
+impl PayloadFormat {
+    /// Serialises the payload, returning a `SerialisationError` if it cannot be serialised.
+    pub fn serialise<T: Serialize + ?Sized>(&self, payload: &T) -> Result<Vec<u8>, Error> {
+        match self {
+            PayloadFormat::Json => {
+                serde_json::to_vec(payload).map_err(|e| Error::SerialisationError(e.to_string()))
+            }
+            #[cfg(feature = "cbor")]
+            PayloadFormat::Cbor => {
+                let mut serialised = Vec::new();
+                ciborium::into_writer(payload, &mut serialised)
+                    .map_err(|e| Error::SerialisationError(e.to_string()))?;
+                Ok(serialised)
+            }
+            #[cfg(feature = "msgpack")]
+            PayloadFormat::MessagePack => rmp_serde::to_vec_named(payload)
+                .map_err(|e| Error::SerialisationError(e.to_string())),
+            #[cfg(feature = "postcard")]
+            PayloadFormat::Postcard => {
+                postcard::to_allocvec(payload).map_err(|e| Error::SerialisationError(e.to_string()))
+            }
+            #[cfg(feature = "bincode")]
+            PayloadFormat::Bincode => {
+                bincode::serialize(payload).map_err(|e| Error::SerialisationError(e.to_string()))
+            }
+        }
+    }
+
+    /// De-serialises the payload, returning an `InvalidPayload` error if it does not match the expected type.
+    ///
+    /// For JSON, a `PayloadUtf8Error` is returned if the payload is not valid UTF-8.
+    pub fn deserialise<T: for<'de> Deserialize<'de>>(&self, payload: &[u8]) -> Result<T, Error> {
+        // usually de-serialisation errors are
+        // caused when the developer was expecting the
+        // wrong payload type or has recently changed the payload type
+        match self {
+            PayloadFormat::Json => {
+                let payload = std::str::from_utf8(payload).map_err(|_| Error::PayloadUtf8Error)?;
+                serde_json::from_str(payload).map_err(|_| Error::InvalidPayload)
+            }
+            #[cfg(feature = "cbor")]
+            PayloadFormat::Cbor => {
+                ciborium::from_reader(payload).map_err(|_| Error::InvalidPayload)
+            }
+            #[cfg(feature = "msgpack")]
+            PayloadFormat::MessagePack => {
+                rmp_serde::from_slice(payload).map_err(|_| Error::InvalidPayload)
+            }
+            #[cfg(feature = "postcard")]
+            PayloadFormat::Postcard => {
+                postcard::from_bytes(payload).map_err(|_| Error::InvalidPayload)
+            }
+            #[cfg(feature = "bincode")]
+            PayloadFormat::Bincode => {
+                bincode::deserialize(payload).map_err(|_| Error::InvalidPayload)
+            }
+        }
+    }
+}
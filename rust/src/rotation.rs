@@ -0,0 +1,175 @@
+use crate::{Algorithm, Clock, Encoder, Error, HmacSigner, KeyInfo, Payload};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// An enum describing which key verified a token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchedKey {
+    // The current key used for signing
+    Current,
+
+    // The fallback key at the given index of the fallback keys
+    Fallback(usize),
+}
+
+impl MatchedKey {
+    /// Returns `true` if the token was signed with the current key.
+    ///
+    /// Otherwise, the token should be re-issued with the current key before the fallback key is retired.
+    #[inline]
+    pub fn is_current(&self) -> bool {
+        matches!(self, MatchedKey::Current)
+    }
+}
+
+/// A struct that holds the HMAC signer logic for rotating keys.
+///
+/// The `RotatingSigner` struct always signs the payload with the current key but accepts tokens signed
+/// by the current key or any of the fallback keys, which are tried in the order they were provided.
+/// This allows the secret key to be rotated without instantly invalidating every outstanding token.
+///
+/// Sample Usage:
+/// ```rust
+/// use hmac_serialiser::{Algorithm, Encoder, HmacSigner, KeyInfo, MatchedKey, Payload, RotatingSigner};
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Serialize, Deserialize, Debug)]
+/// struct UserData {
+///     username: String,
+/// }
+/// impl Payload for UserData {
+///     fn get_exp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+///         None
+///     }
+/// }
+///
+/// let old_key = KeyInfo {
+///     key: b"old_secret_key".to_vec(),
+///     salt: vec![],
+///     info: vec![],
+/// };
+/// let new_key = KeyInfo {
+///     key: b"new_secret_key".to_vec(),
+///     salt: vec![],
+///     info: vec![],
+/// };
+///
+/// let old_signer = HmacSigner::new(old_key.clone(), Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+/// let token = old_signer.sign(&UserData { username: "user123".to_string() });
+///
+/// let signer = RotatingSigner::new(new_key, vec![old_key], Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+/// let (user, matched_key): (UserData, _) = signer.unsign_with_key(&token).unwrap();
+/// assert_eq!(matched_key, MatchedKey::Fallback(0));
+///
+/// // re-issue the token with the current key
+/// let token = signer.sign(&user);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RotatingSigner {
+    current: HmacSigner,
+    fallbacks: Vec<HmacSigner>,
+}
+
+impl RotatingSigner {
+    /// Initialises the signer, panicking if any of the keys is invalid.
+    ///
+    /// This is a convenience wrapper around [`RotatingSigner::try_new`].
+    pub fn new(
+        current: KeyInfo,
+        fallbacks: Vec<KeyInfo>,
+        algo: Algorithm,
+        encoder: Encoder,
+    ) -> Self {
+        Self::try_new(current, fallbacks, algo, encoder).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Initialises the signer, returning an error if the current key or any of the fallback keys is invalid.
+    ///
+    /// The keys are validated in the same way as [`HmacSigner::try_new`].
+    pub fn try_new(
+        current: KeyInfo,
+        fallbacks: Vec<KeyInfo>,
+        algo: Algorithm,
+        encoder: Encoder,
+    ) -> Result<Self, Error> {
+        let fallbacks = fallbacks
+            .into_iter()
+            .map(|key_info| HmacSigner::try_new(key_info, algo.clone(), encoder.clone()))
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            current: HmacSigner::try_new(current, algo, encoder)?,
+            fallbacks,
+        })
+    }
+
+    /// Sets the clock used to get the current time when checking the expiration time of a token
+    /// for the current key and every fallback key, see [`HmacSigner::with_clock`].
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        let clock: Arc<dyn Clock> = Arc::new(clock);
+        for signer in std::iter::once(&mut self.current).chain(&mut self.fallbacks) {
+            signer.clock = clock.clone();
+        }
+        self
+    }
+
+    /// Sets the tolerance for clock drift when checking the expiration time of a token
+    /// for the current key and every fallback key, see [`HmacSigner::with_leeway`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the leeway is negative or greater than [`MAX_LEEWAY`](crate::MAX_LEEWAY).
+    pub fn with_leeway(mut self, leeway: chrono::Duration) -> Self {
+        if let Err(e) = HmacSigner::validate_leeway(leeway) {
+            panic!("{}", e);
+        }
+        for signer in std::iter::once(&mut self.current).chain(&mut self.fallbacks) {
+            signer.leeway = leeway;
+        }
+        self
+    }
+
+    /// Signs the payload with the current key and returns the token which can be sent to the client.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the payload cannot be serialised, see [`RotatingSigner::try_sign`] for a non-panicking alternative.
+    pub fn sign<T: Serialize + Payload>(&self, payload: &T) -> String {
+        self.current.sign(payload)
+    }
+
+    /// Signs the payload with the current key and returns the token,
+    /// or a `SerialisationError` if the payload cannot be serialised.
+    pub fn try_sign<T: Serialize + Payload>(&self, payload: &T) -> Result<String, Error> {
+        self.current.try_sign(payload)
+    }
+
+    /// Verifies the token and returns the deserialised payload along with the key that verified it.
+    ///
+    /// The token is first verified with the current key before trying each of the fallback keys in order.
+    /// If none of the keys verifies the token, an `InvalidToken` error is returned.
+    /// The other errors are the same as the ones returned by [`HmacSigner::unsign`].
+    pub fn unsign_with_key<T: for<'de> Deserialize<'de> + Payload>(
+        &self,
+        token: &str,
+    ) -> Result<(T, MatchedKey), Error> {
+        let (encoded_payload, signature) = self.current.split_token(token)?;
+
+        let signers = std::iter::once((MatchedKey::Current, &self.current)).chain(
+            self.fallbacks
+                .iter()
+                .enumerate()
+                .map(|(i, signer)| (MatchedKey::Fallback(i), signer)),
+        );
+        for (matched_key, signer) in signers {
+            if signer.verify(encoded_payload.as_bytes(), &signature) {
+                return Ok((signer.load_payload(encoded_payload)?, matched_key));
+            }
+        }
+        Err(Error::InvalidToken)
+    }
+
+    /// Verifies the token with the current key or any of the fallback keys and returns the deserialised payload.
+    pub fn unsign<T: for<'de> Deserialize<'de> + Payload>(&self, token: &str) -> Result<T, Error> {
+        self.unsign_with_key(token).map(|(payload, _)| payload)
+    }
+}
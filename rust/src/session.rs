@@ -0,0 +1,241 @@
+//! Cookie-backed sessions with server-side revocation, enabled via the `cookie` feature.
+//!
+//! Stateless sessions cannot normally be logged out as a signed cookie stays valid until it expires.
+//! [`SessionManager`] embeds the user's session generation into the signed cookie and compares it against
+//! the generation in a [`RevocationStore`] when verifying the cookie, so that incrementing the generation via
+//! [`SessionManager::logout_everywhere`] invalidates every outstanding session cookie of the user.
+//!
+//! Sample Usage:
+//! ```rust
+//! use cookie::CookieJar;
+//! use hmac_serialiser::cookie::CookieSigner;
+//! use hmac_serialiser::session::{InMemoryRevocationStore, SessionManager};
+//! use hmac_serialiser::{Algorithm, Encoder, Error, HmacSigner, KeyInfo};
+//!
+//! let key_info = KeyInfo {
+//!     key: b"your_secret_key".to_vec(),
+//!     salt: vec![],
+//!     info: vec![],
+//! };
+//! let signer = HmacSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+//! let sessions = SessionManager::new(CookieSigner::new(signer), InMemoryRevocationStore::new());
+//!
+//! let mut laptop = CookieJar::new();
+//! let mut phone = CookieJar::new();
+//! sessions.login(&mut laptop, "user123", &"dark-theme").unwrap();
+//! sessions.login(&mut phone, "user123", &"light-theme").unwrap();
+//!
+//! sessions.logout_everywhere("user123").unwrap();
+//! let result = sessions.load::<String>(&laptop).unwrap();
+//! assert_eq!(result.unwrap_err(), Error::SessionRevoked);
+//! ```
+use crate::cookie::CookieSigner;
+use crate::{Error, Payload};
+use ::cookie::{Cookie, CookieJar};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// The default name of the session cookie.
+pub const DEFAULT_SESSION_COOKIE_NAME: &str = "session";
+
+/// The default lifetime of a session, see [`SessionManager::with_lifetime`].
+pub const DEFAULT_SESSION_LIFETIME: chrono::Duration = chrono::Duration::days(7);
+
+/// A trait for the server-side store of each user's session generation.
+///
+/// A session is only valid if it was issued with the user's current generation,
+/// hence incrementing the generation revokes all of the user's existing sessions.
+/// Implementations backed by a database or a cache can return a `RevocationStoreError` if the store is unavailable.
+pub trait RevocationStore: Send + Sync {
+    /// Returns the current session generation of the user, which is `0` for users that never had their sessions revoked.
+    fn generation(&self, user_id: &str) -> Result<u64, Error>;
+
+    /// Increments the session generation of the user and returns the new generation.
+    fn revoke_all(&self, user_id: &str) -> Result<u64, Error>;
+}
+
+impl<S: RevocationStore + ?Sized> RevocationStore for Arc<S> {
+    fn generation(&self, user_id: &str) -> Result<u64, Error> {
+        (**self).generation(user_id)
+    }
+
+    fn revoke_all(&self, user_id: &str) -> Result<u64, Error> {
+        (**self).revoke_all(user_id)
+    }
+}
+
+/// A revocation store that keeps the session generations in memory.
+///
+/// The generations are lost when the process restarts, which un-revokes the sessions revoked before the restart,
+/// and are not shared between processes. Use a store backed by a database or a cache in those cases.
+#[derive(Default, Debug)]
+pub struct InMemoryRevocationStore {
+    generations: RwLock<HashMap<String, u64>>,
+}
+
+impl InMemoryRevocationStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl RevocationStore for InMemoryRevocationStore {
+    fn generation(&self, user_id: &str) -> Result<u64, Error> {
+        let generations = self
+            .generations
+            .read()
+            .map_err(|e| Error::RevocationStoreError(e.to_string()))?;
+        Ok(generations.get(user_id).copied().unwrap_or(0))
+    }
+
+    fn revoke_all(&self, user_id: &str) -> Result<u64, Error> {
+        let mut generations = self
+            .generations
+            .write()
+            .map_err(|e| Error::RevocationStoreError(e.to_string()))?;
+        let generation = generations.entry(user_id.to_string()).or_insert(0);
+        *generation += 1;
+        Ok(*generation)
+    }
+}
+
+/// A verified session returned by [`SessionManager`].
+///
+/// Only the expiration time is checked when verifying the session. The issued-at time is informational
+/// so that a server whose clock is behind the server that issued the session does not reject it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Session<T> {
+    // ID of the user the session belongs to
+    #[serde(rename = "sub")]
+    pub user_id: String,
+
+    // Session generation of the user when the session was issued
+    #[serde(rename = "gen")]
+    pub generation: u64,
+
+    // Time the session was issued at
+    #[serde(rename = "iat", with = "chrono::serde::ts_seconds")]
+    pub issued_at: chrono::DateTime<chrono::Utc>,
+
+    // Time the session expires at
+    #[serde(rename = "exp", with = "chrono::serde::ts_seconds")]
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+
+    // Application specific session data
+    pub data: T,
+}
+
+impl<T> Payload for Session<T> {
+    fn get_exp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        Some(self.expires_at)
+    }
+}
+
+/// A struct that issues and verifies session cookies, checking them against a [`RevocationStore`].
+#[derive(Clone)]
+pub struct SessionManager {
+    cookies: CookieSigner,
+    store: Arc<dyn RevocationStore>,
+    cookie_name: String,
+    lifetime: chrono::Duration,
+}
+
+impl std::fmt::Debug for SessionManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SessionManager")
+            .field("cookies", &self.cookies)
+            .field("cookie_name", &self.cookie_name)
+            .field("lifetime", &self.lifetime)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SessionManager {
+    pub fn new<S: RevocationStore + 'static>(cookies: CookieSigner, store: S) -> Self {
+        Self {
+            cookies,
+            store: Arc::new(store),
+            cookie_name: DEFAULT_SESSION_COOKIE_NAME.to_string(),
+            lifetime: DEFAULT_SESSION_LIFETIME,
+        }
+    }
+
+    /// Sets the name of the session cookie, which is [`DEFAULT_SESSION_COOKIE_NAME`] by default.
+    pub fn with_cookie_name(mut self, cookie_name: &str) -> Self {
+        self.cookie_name = cookie_name.to_string();
+        self
+    }
+
+    /// Sets how long a session is valid for after it is issued, which is [`DEFAULT_SESSION_LIFETIME`] by default.
+    pub fn with_lifetime(mut self, lifetime: chrono::Duration) -> Self {
+        self.lifetime = lifetime;
+        self
+    }
+
+    /// Signs a new session for the user with the user's current generation into a cookie.
+    ///
+    /// A `RevocationStoreError` is returned if the generation cannot be read from the store
+    /// and an `InvalidInput` error is returned if the session's expiration time is out of range due to its lifetime.
+    /// The other errors are the same as the ones returned by [`CookieSigner::cookie`].
+    pub fn sign<T: Serialize>(&self, user_id: &str, data: &T) -> Result<Cookie<'static>, Error> {
+        let now = self.cookies.signer().now();
+        let expires_at = now
+            .checked_add_signed(self.lifetime)
+            .ok_or_else(|| Error::InvalidInput("session lifetime is out of range".to_string()))?;
+        let session = Session {
+            user_id: user_id.to_string(),
+            generation: self.store.generation(user_id)?,
+            issued_at: now,
+            expires_at,
+            data,
+        };
+        self.cookies.cookie(&self.cookie_name, &session)
+    }
+
+    /// Verifies the session cookie and checks that it has not been revoked.
+    ///
+    /// A `SessionRevoked` error is returned if the session was issued before the user's sessions were revoked
+    /// and a `RevocationStoreError` is returned if the generation cannot be read from the store.
+    /// The other errors are the same as the ones returned by [`CookieSigner::verify`].
+    pub fn unsign<T: for<'de> Deserialize<'de>>(
+        &self,
+        cookie: &Cookie<'_>,
+    ) -> Result<Session<T>, Error> {
+        let session: Session<T> = self.cookies.verify(cookie)?;
+        if session.generation < self.store.generation(&session.user_id)? {
+            return Err(Error::SessionRevoked);
+        }
+        Ok(session)
+    }
+
+    /// Signs a new session for the user and adds its cookie to the jar.
+    pub fn login<T: Serialize>(
+        &self,
+        jar: &mut CookieJar,
+        user_id: &str,
+        data: &T,
+    ) -> Result<(), Error> {
+        jar.add(self.sign(user_id, data)?);
+        Ok(())
+    }
+
+    /// Verifies the session cookie in the jar, or returns `None` if there is no session cookie.
+    pub fn load<T: for<'de> Deserialize<'de>>(
+        &self,
+        jar: &CookieJar,
+    ) -> Option<Result<Session<T>, Error>> {
+        jar.get(&self.cookie_name).map(|cookie| self.unsign(cookie))
+    }
+
+    /// Removes the session cookie from the jar, which only logs out the current client.
+    pub fn logout(&self, jar: &mut CookieJar) {
+        self.cookies.jar(jar).remove(&self.cookie_name);
+    }
+
+    /// Revokes every outstanding session of the user, logging out all of the user's clients.
+    pub fn logout_everywhere(&self, user_id: &str) -> Result<(), Error> {
+        self.store.revoke_all(user_id)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,228 @@
+use crate::{Error, HmacSigner, DETACHED_PREFIX};
+use base64::{engine::general_purpose, Engine as _};
+use std::io::Read;
+
+#[cfg(not(feature = "ring"))]
+use crate::Algorithm;
+
+#[cfg(not(feature = "ring"))]
+use hmac::Mac;
+
+#[cfg(feature = "ring")]
+use ring::hmac;
+
+#[cfg(feature = "ring")]
+use subtle::ConstantTimeEq as _;
+
+// size of the buffer used when reading from a reader
+const CHUNK_SIZE: usize = 8192;
+
+#[cfg(not(feature = "ring"))]
+#[derive(Clone)]
+enum MacState {
+    Sha1(hmac::Hmac<sha1::Sha1>),
+    Sha256(hmac::Hmac<sha2::Sha256>),
+    Sha384(hmac::Hmac<sha2::Sha384>),
+    Sha512(hmac::Hmac<sha2::Sha512>),
+}
+
+#[cfg(feature = "ring")]
+type MacState = hmac::Context;
+
+/// A struct that computes the HMAC signature of a payload that is fed in chunks.
+///
+/// The signature is the same as the one produced by [`HmacSigner::sign_detached`] for the concatenated chunks,
+/// allowing large payloads like files to be signed without loading them fully into memory.
+/// Like detached signatures, it is computed under a different domain than tokens and cannot be used to forge one.
+///
+/// Sample Usage:
+/// ```rust
+/// use hmac_serialiser::{Algorithm, Encoder, HmacSigner, KeyInfo};
+///
+/// let key_info = KeyInfo {
+///     key: b"your_secret_key".to_vec(),
+///     salt: vec![],
+///     info: vec![],
+/// };
+/// let signer = HmacSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+///
+/// let mut stream = signer.stream();
+/// stream.update(b"hello ");
+/// stream.update(b"world");
+/// let signature = stream.finalize();
+/// assert_eq!(signature, signer.sign_detached(b"hello world"));
+/// ```
+#[derive(Clone)]
+pub struct HmacStream {
+    state: MacState,
+    encoder: general_purpose::GeneralPurpose,
+}
+
+impl std::fmt::Debug for HmacStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HmacStream").finish_non_exhaustive()
+    }
+}
+
+impl HmacStream {
+    /// Feeds the next chunk of the payload into the MAC.
+    #[cfg(not(feature = "ring"))]
+    pub fn update(&mut self, chunk: &[u8]) {
+        match &mut self.state {
+            MacState::Sha1(mac) => mac.update(chunk),
+            MacState::Sha256(mac) => mac.update(chunk),
+            MacState::Sha384(mac) => mac.update(chunk),
+            MacState::Sha512(mac) => mac.update(chunk),
+        }
+    }
+
+    /// Feeds the next chunk of the payload into the MAC.
+    #[cfg(feature = "ring")]
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.state.update(chunk);
+    }
+
+    #[cfg(not(feature = "ring"))]
+    fn tag(state: MacState) -> Vec<u8> {
+        match state {
+            MacState::Sha1(mac) => mac.finalize().into_bytes().to_vec(),
+            MacState::Sha256(mac) => mac.finalize().into_bytes().to_vec(),
+            MacState::Sha384(mac) => mac.finalize().into_bytes().to_vec(),
+            MacState::Sha512(mac) => mac.finalize().into_bytes().to_vec(),
+        }
+    }
+
+    #[cfg(feature = "ring")]
+    fn tag(state: MacState) -> Vec<u8> {
+        state.sign().as_ref().to_vec()
+    }
+
+    /// Returns the encoded signature of all the chunks fed so far.
+    pub fn finalize(self) -> String {
+        self.encoder.encode(Self::tag(self.state))
+    }
+
+    /// Verifies the encoded signature against all the chunks fed so far in constant time.
+    ///
+    /// If the signature cannot be decoded, an `InvalidSignature` error is returned.
+    /// If the signature does not match, an `InvalidToken` error is returned.
+    pub fn verify(self, signature: &str) -> Result<(), Error> {
+        let signature = self
+            .encoder
+            .decode(signature)
+            .map_err(|_| Error::InvalidSignature)?;
+        if !Self::verify_tag(self.state, &signature) {
+            return Err(Error::InvalidToken);
+        }
+        Ok(())
+    }
+
+    #[cfg(not(feature = "ring"))]
+    fn verify_tag(state: MacState, signature: &[u8]) -> bool {
+        match state {
+            MacState::Sha1(mac) => mac.verify_slice(signature).is_ok(),
+            MacState::Sha256(mac) => mac.verify_slice(signature).is_ok(),
+            MacState::Sha384(mac) => mac.verify_slice(signature).is_ok(),
+            MacState::Sha512(mac) => mac.verify_slice(signature).is_ok(),
+        }
+    }
+
+    #[cfg(feature = "ring")]
+    fn verify_tag(state: MacState, signature: &[u8]) -> bool {
+        // ring's hmac::verify requires the whole payload upfront, hence the finalised tag is compared instead
+        // (the length is not secret, so returning early on a length mismatch does not leak anything)
+        Self::tag(state).as_slice().ct_eq(signature).into()
+    }
+}
+
+impl HmacSigner {
+    /// Starts computing the signature of a payload that is fed in chunks via [`HmacStream::update`].
+    ///
+    /// The stream is seeded with the same domain separation prefix as [`HmacSigner::sign_detached`].
+    #[cfg(not(feature = "ring"))]
+    pub fn stream(&self) -> HmacStream {
+        let key = &self.expanded_key;
+        let state = match self.algo {
+            Algorithm::SHA1 => MacState::Sha1(
+                hmac::Hmac::new_from_slice(key).expect("HMAC can take key of any size"),
+            ),
+            Algorithm::SHA256 => MacState::Sha256(
+                hmac::Hmac::new_from_slice(key).expect("HMAC can take key of any size"),
+            ),
+            Algorithm::SHA384 => MacState::Sha384(
+                hmac::Hmac::new_from_slice(key).expect("HMAC can take key of any size"),
+            ),
+            Algorithm::SHA512 => MacState::Sha512(
+                hmac::Hmac::new_from_slice(key).expect("HMAC can take key of any size"),
+            ),
+        };
+        let mut stream = HmacStream {
+            state,
+            encoder: self.encoder.clone(),
+        };
+        stream.update(DETACHED_PREFIX);
+        stream
+    }
+
+    /// Starts computing the signature of a payload that is fed in chunks via [`HmacStream::update`].
+    ///
+    /// The stream is seeded with the same domain separation prefix as [`HmacSigner::sign_detached`].
+    #[cfg(feature = "ring")]
+    pub fn stream(&self) -> HmacStream {
+        let mut stream = HmacStream {
+            state: hmac::Context::with_key(&self.expanded_key),
+            encoder: self.encoder.clone(),
+        };
+        stream.update(DETACHED_PREFIX);
+        stream
+    }
+
+    /// Reads the reader until the end and feeds it into a new [`HmacStream`].
+    fn stream_reader<R: Read>(&self, mut reader: R) -> Result<HmacStream, Error> {
+        let mut stream = self.stream();
+        let mut buf = [0u8; CHUNK_SIZE];
+        loop {
+            match reader.read(&mut buf) {
+                Ok(0) => return Ok(stream),
+                Ok(n) => stream.update(&buf[..n]),
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(Error::IoError(e.to_string())),
+            }
+        }
+    }
+
+    /// Reads the reader until the end and returns its encoded signature,
+    /// which is the same as the one returned by [`HmacSigner::sign_detached`].
+    ///
+    /// An `IoError` is returned if the reader fails.
+    ///
+    /// Sample Usage:
+    /// ```rust
+    /// use hmac_serialiser::{Algorithm, Encoder, HmacSigner, KeyInfo};
+    ///
+    /// let key_info = KeyInfo {
+    ///     key: b"your_secret_key".to_vec(),
+    ///     info: b"uploads".to_vec(),
+    ///     salt: vec![],
+    /// };
+    /// let signer = HmacSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+    ///
+    /// // e.g. std::fs::File::open("manifest.json")
+    /// let file = std::io::Cursor::new(vec![0u8; 1024 * 1024]);
+    /// let signature = signer.sign_reader(file).unwrap();
+    ///
+    /// let file = std::io::Cursor::new(vec![0u8; 1024 * 1024]);
+    /// assert!(signer.verify_reader(file, &signature).is_ok());
+    /// ```
+    pub fn sign_reader<R: Read>(&self, reader: R) -> Result<String, Error> {
+        Ok(self.stream_reader(reader)?.finalize())
+    }
+
+    /// Reads the reader until the end and verifies its encoded signature in constant time.
+    ///
+    /// An `IoError` is returned if the reader fails.
+    /// The other errors are the same as the ones returned by [`HmacSigner::verify_detached`].
+    pub fn verify_reader<R: Read>(&self, reader: R, signature: &str) -> Result<(), Error> {
+        self.stream_reader(reader)?.verify(signature)
+    }
+}
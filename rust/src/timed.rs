@@ -0,0 +1,198 @@
+use crate::{Algorithm, Clock, Encoder, Error, HmacSigner, KeyInfo, DELIM};
+use base64::Engine as _;
+use serde::{Deserialize, Serialize};
+
+/// A struct that holds the timed HMAC signer logic.
+///
+/// The `TimedSigner` struct embeds the time at which the payload was signed into the token,
+/// producing an output structure of `<payload>.<timestamp>.<signature>`.
+/// Unlike [`HmacSigner`], the payload does not have to carry its own expiry field as the
+/// maximum age of the token is instead supplied when verifying it.
+///
+/// The timestamp is the base64 encoded Unix time in seconds and is covered by the signature.
+#[derive(Debug, Clone)]
+pub struct TimedSigner {
+    signer: HmacSigner,
+}
+
+impl TimedSigner {
+    /// Initialises the signer, panicking if the key is invalid.
+    ///
+    /// This is a convenience wrapper around [`TimedSigner::try_new`].
+    pub fn new(key_info: KeyInfo, algo: Algorithm, encoder: Encoder) -> Self {
+        Self::try_new(key_info, algo, encoder).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Initialises the signer, returning an error if the key is invalid.
+    ///
+    /// The key is validated in the same way as [`HmacSigner::try_new`].
+    pub fn try_new(key_info: KeyInfo, algo: Algorithm, encoder: Encoder) -> Result<Self, Error> {
+        Ok(Self {
+            signer: HmacSigner::try_new(key_info, algo, encoder)?,
+        })
+    }
+
+    /// Sets the clock used to get the current time when signing and checking the age of a token,
+    /// see [`HmacSigner::with_clock`].
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.signer = self.signer.with_clock(clock);
+        self
+    }
+
+    /// Sets the tolerance for clock drift when checking the age of a token,
+    /// see [`HmacSigner::with_leeway`].
+    pub fn with_leeway(mut self, leeway: chrono::Duration) -> Self {
+        self.signer = self.signer.with_leeway(leeway);
+        self
+    }
+
+    /// Signs the payload with the current time of the signer's clock and returns the token which can be sent to the client.
+    ///
+    /// Sample Usage:
+    /// ```rust
+    /// use hmac_serialiser::{TimedSigner, KeyInfo, Encoder, Algorithm};
+    ///
+    /// let key_info = KeyInfo {
+    ///    key: b"your_secret_key".to_vec(),
+    ///    salt: b"password-reset".to_vec(),
+    ///    info: vec![],
+    /// };
+    ///
+    /// let signer = TimedSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+    /// let token = signer.sign(&"user123@example.com");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the payload cannot be serialised, see [`TimedSigner::try_sign`] for a non-panicking alternative.
+    pub fn sign<T: Serialize>(&self, payload: &T) -> String {
+        self.sign_at(payload, self.signer.now())
+    }
+
+    /// Signs the payload with the provided timestamp instead of the current time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the payload cannot be serialised, see [`TimedSigner::try_sign_at`] for a non-panicking alternative.
+    pub fn sign_at<T: Serialize>(
+        &self,
+        payload: &T,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    ) -> String {
+        self.try_sign_at(payload, timestamp)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Signs the payload with the current time and returns the token,
+    /// or a `SerialisationError` if the payload cannot be serialised.
+    pub fn try_sign<T: Serialize>(&self, payload: &T) -> Result<String, Error> {
+        self.try_sign_at(payload, self.signer.now())
+    }
+
+    /// Signs the payload with the provided timestamp and returns the token,
+    /// or a `SerialisationError` if the payload cannot be serialised.
+    pub fn try_sign_at<T: Serialize>(
+        &self,
+        payload: &T,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    ) -> Result<String, Error> {
+        let token =
+            serde_json::to_vec(payload).map_err(|e| Error::SerialisationError(e.to_string()))?;
+        let token = self.signer.encoder.encode(&token);
+        let timestamp = self
+            .signer
+            .encoder
+            .encode(timestamp.timestamp().to_string().as_bytes());
+
+        let data = format!("{}{}{}", token, DELIM, timestamp);
+        let signature = self.signer.sign_payload(data.as_bytes());
+        let signature = self.signer.encoder.encode(&signature);
+        Ok(format!("{}{}{}", data, DELIM, signature))
+    }
+
+    /// Verifies the token and returns the deserialised payload along with the time it was signed at.
+    ///
+    /// The age of the token is not checked. Use [`TimedSigner::unsign_with_max_age`] to reject old tokens.
+    ///
+    /// If the token does not contain three parts, an `InvalidInput` error is returned.
+    /// If the signature cannot be decoded, an `InvalidSignature` error is returned.
+    /// If the signature does not match or the embedded timestamp is malformed, an `InvalidToken` error is returned.
+    pub fn unsign_with_timestamp<T: for<'de> Deserialize<'de>>(
+        &self,
+        token: &str,
+    ) -> Result<(T, chrono::DateTime<chrono::Utc>), Error> {
+        let parts: Vec<&str> = token.split(DELIM).collect();
+        if parts.len() != 3 {
+            return Err(Error::InvalidInput(token.to_string()));
+        }
+
+        let (encoded_payload, encoded_timestamp) = (parts[0], parts[1]);
+        if encoded_payload.is_empty() || encoded_timestamp.is_empty() {
+            return Err(Error::InvalidToken);
+        }
+
+        let signature = self
+            .signer
+            .encoder
+            .decode(parts[2])
+            .map_err(|_| Error::InvalidSignature)?;
+        let data = &token[..encoded_payload.len() + DELIM.len_utf8() + encoded_timestamp.len()];
+        if !self.signer.verify(data.as_bytes(), &signature) {
+            return Err(Error::InvalidToken);
+        }
+
+        let timestamp = self
+            .signer
+            .encoder
+            .decode(encoded_timestamp)
+            .ok()
+            .and_then(|timestamp| String::from_utf8(timestamp).ok())
+            .and_then(|timestamp| timestamp.parse::<i64>().ok())
+            .and_then(|timestamp| chrono::DateTime::from_timestamp(timestamp, 0))
+            .ok_or(Error::InvalidToken)?;
+
+        let payload: T = self.signer.deserialise_payload(encoded_payload)?;
+        Ok((payload, timestamp))
+    }
+
+    /// Verifies the token and returns the deserialised payload without checking its age.
+    pub fn unsign<T: for<'de> Deserialize<'de>>(&self, token: &str) -> Result<T, Error> {
+        self.unsign_with_timestamp(token)
+            .map(|(payload, _)| payload)
+    }
+
+    /// Verifies the token and returns the deserialised payload if it was signed within `max_age`.
+    ///
+    /// On top of the errors returned by [`TimedSigner::unsign_with_timestamp`],
+    /// a `TokenExpired` error is returned if the token is older than `max_age` plus the signer's leeway.
+    ///
+    /// Sample Usage:
+    /// ```rust
+    /// use hmac_serialiser::{TimedSigner, KeyInfo, Encoder, Algorithm, Error};
+    ///
+    /// let key_info = KeyInfo {
+    ///    key: b"your_secret_key".to_vec(),
+    ///    salt: b"password-reset".to_vec(),
+    ///    info: vec![],
+    /// };
+    ///
+    /// let signer = TimedSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+    /// let token = signer.sign(&"user123@example.com");
+    /// let email: String = signer
+    ///     .unsign_with_max_age(&token, chrono::Duration::minutes(15))
+    ///     .expect("token should still be valid");
+    /// ```
+    pub fn unsign_with_max_age<T: for<'de> Deserialize<'de>>(
+        &self,
+        token: &str,
+        max_age: chrono::Duration,
+    ) -> Result<T, Error> {
+        let (payload, timestamp) = self.unsign_with_timestamp(token)?;
+        // a maximum age that overflows once the leeway is added does not bound the token
+        let max_age = max_age.checked_add(&self.signer.leeway);
+        if max_age.is_some_and(|max_age| self.signer.now() - timestamp > max_age) {
+            return Err(Error::TokenExpired);
+        }
+        Ok(payload)
+    }
+}
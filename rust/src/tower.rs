@@ -0,0 +1,255 @@
+//! Tower integration, enabled via the `tower` feature.
+//!
+//! [`TokenLayer`] wraps any `tower::Service` handling `http` requests, such as an axum router, a tonic server
+//! or a hyper service. It verifies the token of each request with a [`TokenVerifier`], inserts the deserialised
+//! payload into the request extensions, and short-circuits with a [`Reject`] response if the token is invalid.
+//!
+//! Sample Usage:
+//! ```rust
+//! use hmac_serialiser::tower::TokenLayer;
+//! use hmac_serialiser::web::TokenVerifier;
+//! use hmac_serialiser::{Algorithm, Encoder, HmacSigner, KeyInfo, Payload};
+//! use serde::{Deserialize, Serialize};
+//! use tower::ServiceBuilder;
+//!
+//! #[derive(Serialize, Deserialize, Debug, Clone)]
+//! struct UserData {
+//!     username: String,
+//! }
+//! impl Payload for UserData {
+//!     fn get_exp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+//!         None
+//!     }
+//! }
+//!
+//! let key_info = KeyInfo {
+//!     key: b"your_secret_key".to_vec(),
+//!     salt: vec![],
+//!     info: vec![],
+//! };
+//! let signer = HmacSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+//!
+//! let service = ServiceBuilder::new()
+//!     .layer(TokenLayer::<UserData>::new(TokenVerifier::new(signer)))
+//!     .service_fn(|request: http::Request<String>| async move {
+//!         let user = request.extensions().get::<UserData>().unwrap();
+//!         Ok::<_, std::convert::Infallible>(http::Response::new(user.username.clone()))
+//!     });
+//! ```
+use crate::web::TokenVerifier;
+use crate::{Error, Payload};
+use serde::Deserialize;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// A trait for building the response returned when a request is rejected by [`TokenService`].
+///
+/// It is implemented for closures taking the [`Error`] so that, for example, a tonic service
+/// can respond with a gRPC status instead of an HTTP status code.
+pub trait Reject<B> {
+    fn reject(&self, error: Error) -> http::Response<B>;
+}
+
+impl<B, F: Fn(Error) -> http::Response<B>> Reject<B> for F {
+    fn reject(&self, error: Error) -> http::Response<B> {
+        self(error)
+    }
+}
+
+/// The default rejection which responds with the status code from [`Error::status_code`]
+/// and the same JSON body as the axum and actix integrations.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct JsonRejection;
+
+impl<B: From<String>> Reject<B> for JsonRejection {
+    fn reject(&self, error: Error) -> http::Response<B> {
+        let status = http::StatusCode::from_u16(error.status_code())
+            .unwrap_or(http::StatusCode::INTERNAL_SERVER_ERROR);
+        let mut response = http::Response::new(B::from(error.response_body()));
+        *response.status_mut() = status;
+        response.headers_mut().insert(
+            http::header::CONTENT_TYPE,
+            http::HeaderValue::from_static("application/json"),
+        );
+        response
+    }
+}
+
+/// A layer that verifies the token of each request before it reaches the inner service,
+/// inserting the deserialised payload of type `T` into the request extensions.
+pub struct TokenLayer<T, R = JsonRejection> {
+    verifier: TokenVerifier,
+    rejection: R,
+    _payload: PhantomData<fn() -> T>,
+}
+
+impl<T> TokenLayer<T> {
+    /// Initialises the layer which rejects requests with a [`JsonRejection`].
+    pub fn new(verifier: TokenVerifier) -> Self {
+        Self {
+            verifier,
+            rejection: JsonRejection,
+            _payload: PhantomData,
+        }
+    }
+}
+
+impl<T, R> TokenLayer<T, R> {
+    /// Sets how the response is built when a request is rejected.
+    ///
+    /// Sample Usage:
+    /// ```rust
+    /// use hmac_serialiser::tower::TokenLayer;
+    /// use hmac_serialiser::web::TokenVerifier;
+    /// use hmac_serialiser::{Algorithm, Encoder, Error, HmacSigner, KeyInfo};
+    ///
+    /// let key_info = KeyInfo {
+    ///     key: b"your_secret_key".to_vec(),
+    ///     salt: vec![],
+    ///     info: vec![],
+    /// };
+    /// let signer = HmacSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+    ///
+    /// let layer = TokenLayer::<serde_json::Value>::new(TokenVerifier::new(signer)).with_rejection(
+    ///     |_: Error| {
+    ///         let mut response = http::Response::new(String::new());
+    ///         *response.status_mut() = http::StatusCode::NOT_FOUND;
+    ///         response
+    ///     },
+    /// );
+    /// ```
+    pub fn with_rejection<R2>(self, rejection: R2) -> TokenLayer<T, R2> {
+        TokenLayer {
+            verifier: self.verifier,
+            rejection,
+            _payload: PhantomData,
+        }
+    }
+}
+
+impl<T, R: Clone> Clone for TokenLayer<T, R> {
+    fn clone(&self) -> Self {
+        Self {
+            verifier: self.verifier.clone(),
+            rejection: self.rejection.clone(),
+            _payload: PhantomData,
+        }
+    }
+}
+
+impl<T, R: std::fmt::Debug> std::fmt::Debug for TokenLayer<T, R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TokenLayer")
+            .field("verifier", &self.verifier)
+            .field("rejection", &self.rejection)
+            .finish()
+    }
+}
+
+impl<S, T, R: Clone> Layer<S> for TokenLayer<T, R> {
+    type Service = TokenService<S, T, R>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TokenService {
+            inner,
+            verifier: self.verifier.clone(),
+            rejection: self.rejection.clone(),
+            _payload: PhantomData,
+        }
+    }
+}
+
+/// The service produced by [`TokenLayer`].
+pub struct TokenService<S, T, R = JsonRejection> {
+    inner: S,
+    verifier: TokenVerifier,
+    rejection: R,
+    _payload: PhantomData<fn() -> T>,
+}
+
+impl<S: Clone, T, R: Clone> Clone for TokenService<S, T, R> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            verifier: self.verifier.clone(),
+            rejection: self.rejection.clone(),
+            _payload: PhantomData,
+        }
+    }
+}
+
+impl<S: std::fmt::Debug, T, R: std::fmt::Debug> std::fmt::Debug for TokenService<S, T, R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TokenService")
+            .field("inner", &self.inner)
+            .field("verifier", &self.verifier)
+            .field("rejection", &self.rejection)
+            .finish()
+    }
+}
+
+impl<S, T, R, ReqBody, ResBody> Service<http::Request<ReqBody>> for TokenService<S, T, R>
+where
+    S: Service<http::Request<ReqBody>, Response = http::Response<ResBody>>,
+    T: for<'de> Deserialize<'de> + Payload + Clone + Send + Sync + 'static,
+    R: Reject<ResBody>,
+{
+    type Response = http::Response<ResBody>;
+    type Error = S::Error;
+    type Future = ResponseFuture<S::Future, ResBody>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: http::Request<ReqBody>) -> Self::Future {
+        match self
+            .verifier
+            .verify_http::<T>(request.headers(), request.uri())
+        {
+            Ok(payload) => {
+                request.extensions_mut().insert(payload);
+                ResponseFuture::Inner {
+                    future: self.inner.call(request),
+                }
+            }
+            Err(error) => ResponseFuture::Rejected {
+                response: Some(self.rejection.reject(error)),
+            },
+        }
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// The response future of [`TokenService`].
+    #[project = ResponseFutureProj]
+    pub enum ResponseFuture<F, B> {
+        Inner {
+            #[pin]
+            future: F,
+        },
+        Rejected {
+            response: Option<http::Response<B>>,
+        },
+    }
+}
+
+impl<F, B, E> Future for ResponseFuture<F, B>
+where
+    F: Future<Output = Result<http::Response<B>, E>>,
+{
+    type Output = Result<http::Response<B>, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project() {
+            ResponseFutureProj::Inner { future } => future.poll(cx),
+            ResponseFutureProj::Rejected { response } => {
+                Poll::Ready(Ok(response.take().expect("future polled after completion")))
+            }
+        }
+    }
+}
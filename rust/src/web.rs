@@ -0,0 +1,255 @@
+//! Framework agnostic building blocks shared by the web framework integrations.
+//!
+//! [`TokenVerifier`] holds the signer and the [`TokenLocation`] to read the token from,
+//! while [`Error::status_code`] and [`Error::error_code`] map the crate's errors to HTTP responses.
+use crate::{Error, HmacSigner, Payload};
+use serde::Deserialize;
+use std::sync::Arc;
+
+/// An enum for defining where the token is read from in a request.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub enum TokenLocation {
+    #[default]
+    // `Authorization: Bearer <token>` header
+    Bearer,
+
+    // Value of a custom header such as `X-Api-Token`
+    Header(String),
+
+    // Value of a cookie
+    Cookie(String),
+
+    // Value of a query string parameter, which is percent-decoded
+    Query(String),
+}
+
+impl TokenLocation {
+    /// Finds the token given a function returning every value of a request header and the raw query string.
+    pub(crate) fn find<'a>(
+        &self,
+        headers: impl Fn(&str) -> Vec<&'a str>,
+        query: Option<&'a str>,
+    ) -> Option<String> {
+        match self {
+            TokenLocation::Bearer => headers("authorization").into_iter().find_map(|value| {
+                let (scheme, token) = value.split_once(' ')?;
+                let token = token.trim();
+                (scheme.eq_ignore_ascii_case("bearer") && !token.is_empty())
+                    .then(|| token.to_string())
+            }),
+            TokenLocation::Header(name) => headers(name)
+                .into_iter()
+                .map(str::trim)
+                .find(|value| !value.is_empty())
+                .map(str::to_string),
+            TokenLocation::Cookie(name) => headers("cookie")
+                .into_iter()
+                .flat_map(|value| value.split(';'))
+                .find_map(|cookie| {
+                    let (key, value) = cookie.trim().split_once('=')?;
+                    (key == name && !value.is_empty()).then(|| value.to_string())
+                }),
+            TokenLocation::Query(name) => query?.split('&').find_map(|pair| {
+                let (key, value) = pair.split_once('=')?;
+                (key == name && !value.is_empty()).then(|| percent_decode(value))
+            }),
+        }
+    }
+}
+
+/// Decodes the `%XX` escapes of a query string value, leaving malformed escapes as they are.
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| value.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// A struct that verifies the tokens of incoming requests.
+///
+/// The signer is shared via an `Arc` so that the verifier can be cheaply cloned into the state of a web framework.
+///
+/// Sample Usage:
+/// ```rust
+/// use hmac_serialiser::{Algorithm, Encoder, HmacSigner, KeyInfo};
+/// use hmac_serialiser::web::{TokenLocation, TokenVerifier};
+///
+/// let key_info = KeyInfo {
+///     key: b"your_secret_key".to_vec(),
+///     salt: vec![],
+///     info: vec![],
+/// };
+/// let signer = HmacSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+/// let verifier = TokenVerifier::new(signer).with_location(TokenLocation::Cookie("session".to_string()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TokenVerifier {
+    signer: Arc<HmacSigner>,
+    location: TokenLocation,
+}
+
+impl TokenVerifier {
+    /// Initialises the verifier which reads the token from the `Authorization: Bearer <token>` header by default.
+    pub fn new(signer: impl Into<Arc<HmacSigner>>) -> Self {
+        Self {
+            signer: signer.into(),
+            location: TokenLocation::default(),
+        }
+    }
+
+    /// Sets where the token is read from in a request.
+    pub fn with_location(mut self, location: TokenLocation) -> Self {
+        self.location = location;
+        self
+    }
+
+    /// Returns the signer used to verify the tokens.
+    pub fn signer(&self) -> &HmacSigner {
+        &self.signer
+    }
+
+    /// Returns where the token is read from in a request.
+    pub fn location(&self) -> &TokenLocation {
+        &self.location
+    }
+
+    /// Finds the token in the request and verifies it, returning a `MissingToken` error if there is no token.
+    pub(crate) fn verify<'a, T: for<'de> Deserialize<'de> + Payload>(
+        &self,
+        headers: impl Fn(&str) -> Vec<&'a str>,
+        query: Option<&'a str>,
+    ) -> Result<T, Error> {
+        let token = self
+            .location
+            .find(headers, query)
+            .ok_or(Error::MissingToken)?;
+        self.signer.unsign(&token)
+    }
+
+    /// Finds the token in the headers or the query string of an `http` request and verifies it.
+    #[cfg(any(feature = "axum", feature = "tower"))]
+    pub(crate) fn verify_http<T: for<'de> Deserialize<'de> + Payload>(
+        &self,
+        headers: &http::HeaderMap,
+        uri: &http::Uri,
+    ) -> Result<T, Error> {
+        self.verify(
+            |name| {
+                headers
+                    .get_all(name)
+                    .iter()
+                    .filter_map(|value| value.to_str().ok())
+                    .collect()
+            },
+            uri.query(),
+        )
+    }
+}
+
+impl Error {
+    /// Returns the HTTP status code that a web framework integration responds with for this error.
+    ///
+    /// Errors caused by the client's token are mapped to `401 Unauthorized`, malformed input is mapped to
+    /// `400 Bad Request`, and errors caused by the server's configuration are mapped to `500 Internal Server Error`.
+    pub fn status_code(&self) -> u16 {
+        match self {
+            Error::InvalidInput(_) | Error::PayloadTooLarge(_) | Error::CookieTooLarge(_) => 400,
+            Error::InvalidSignature
+            | Error::InvalidPayload
+            | Error::InvalidToken
+            | Error::TokenExpired
+            | Error::DecryptionError
+            | Error::PayloadDecodeError
+            | Error::PayloadUtf8Error
+            | Error::UnknownKeyId(_)
+            | Error::TokenNotYetValid
+            | Error::TokenIssuedInFuture
+            | Error::MissingToken
+            | Error::SessionRevoked => 401,
+            Error::HkdfExpandError
+            | Error::HkdfFillError
+            | Error::SerialisationError(_)
+            | Error::EncryptionError
+            | Error::IoError(_)
+            | Error::InvalidSeparator(_)
+            | Error::EmptyKey
+            | Error::KeyTooShort(_)
+            | Error::WeakKey
+            | Error::InvalidKeyId(_)
+            | Error::RevocationStoreError(_) => 500,
+        }
+    }
+
+    /// Returns a machine readable code for this error, such as `token_expired`,
+    /// allowing clients to tell an expired token apart from an invalid one.
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            Error::InvalidInput(_) => "invalid_input",
+            Error::InvalidSignature => "invalid_signature",
+            Error::InvalidPayload => "invalid_payload",
+            Error::InvalidToken => "invalid_token",
+            Error::TokenExpired => "token_expired",
+            Error::DecryptionError => "decryption_error",
+            Error::PayloadTooLarge(_) => "payload_too_large",
+            Error::CookieTooLarge(_) => "cookie_too_large",
+            Error::PayloadDecodeError => "payload_decode_error",
+            Error::PayloadUtf8Error => "payload_utf8_error",
+            Error::UnknownKeyId(_) => "unknown_key_id",
+            Error::TokenNotYetValid => "token_not_yet_valid",
+            Error::TokenIssuedInFuture => "token_issued_in_future",
+            Error::MissingToken => "missing_token",
+            Error::SessionRevoked => "session_revoked",
+            _ => "internal_error",
+        }
+    }
+
+    /// Returns a fixed message for this error that is safe to send to the client.
+    ///
+    /// Unlike the `Display` output, it never contains the client's token or key id, which would otherwise
+    /// end up in logs and caches, nor details about the server's configuration.
+    fn response_message(&self) -> &'static str {
+        match self {
+            Error::InvalidInput(_) => "malformed token",
+            Error::InvalidSignature => "invalid signature provided",
+            Error::InvalidPayload => "invalid payload structure",
+            Error::InvalidToken => "invalid token provided",
+            Error::TokenExpired => "token has expired",
+            Error::DecryptionError => "could not decrypt payload",
+            Error::PayloadTooLarge(_) => "payload is too large",
+            Error::CookieTooLarge(_) => "cookie is too large",
+            Error::PayloadDecodeError => "could not decode payload",
+            Error::PayloadUtf8Error => "payload is not valid utf-8",
+            Error::UnknownKeyId(_) => "unknown key id",
+            Error::TokenNotYetValid => "token is not valid yet",
+            Error::TokenIssuedInFuture => "token was issued in the future",
+            Error::MissingToken => "no token provided",
+            Error::SessionRevoked => "session has been revoked",
+            _ => "internal server error",
+        }
+    }
+
+    /// Returns the JSON body that a web framework integration responds with for this error.
+    pub(crate) fn response_body(&self) -> String {
+        serde_json::json!({
+            "error": self.error_code(),
+            "message": self.response_message(),
+        })
+        .to_string()
+    }
+}
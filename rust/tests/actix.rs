@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tests {
+    use actix_web::http::{header, StatusCode};
+    use actix_web::{middleware, test, web, App};
+    use hmac_serialiser::actix::{require_token, Signed};
+    use hmac_serialiser::web::{TokenLocation, TokenVerifier};
+    use hmac_serialiser::{Algorithm, Encoder, HmacSigner, KeyInfo, Payload};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    struct TestClaim {
+        username: String,
+        exp: chrono::DateTime<chrono::Utc>,
+    }
+
+    impl Payload for TestClaim {
+        fn get_exp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+            Some(self.exp)
+        }
+    }
+
+    fn setup() -> HmacSigner {
+        let key_info = KeyInfo {
+            key: b"test_secret_key".to_vec(),
+            salt: b"salt".to_vec(),
+            info: vec![],
+        };
+        HmacSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding)
+    }
+
+    fn token(signer: &HmacSigner, exp: chrono::Duration) -> String {
+        signer.sign(&TestClaim {
+            username: "user123".to_string(),
+            exp: chrono::Utc::now() + exp,
+        })
+    }
+
+    async fn username(Signed(claim): Signed<TestClaim>) -> String {
+        claim.username
+    }
+
+    #[actix_web::test]
+    async fn test_extractor() {
+        let signer = setup();
+        let verifier = TokenVerifier::new(signer.clone())
+            .with_location(TokenLocation::Cookie("session".to_string()));
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(verifier))
+                .route("/", web::get().to(username)),
+        )
+        .await;
+
+        let request = test::TestRequest::get()
+            .uri("/")
+            .insert_header((
+                header::COOKIE,
+                format!("session={}", token(&signer, chrono::Duration::minutes(5))),
+            ))
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(test::read_body(response).await, "user123");
+
+        let request = test::TestRequest::get()
+            .uri("/")
+            .insert_header((
+                header::COOKIE,
+                format!("session={}", token(&signer, chrono::Duration::minutes(-5))),
+            ))
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let body = test::read_body(response).await;
+        assert!(String::from_utf8_lossy(&body).contains("\"token_expired\""));
+    }
+
+    #[actix_web::test]
+    async fn test_extractor_without_verifier() {
+        let app = test::init_service(App::new().route("/", web::get().to(username))).await;
+        let request = test::TestRequest::get().uri("/").to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[actix_web::test]
+    async fn test_require_token_middleware() {
+        let signer = setup();
+        let verifier = TokenVerifier::new(signer.clone())
+            .with_location(TokenLocation::Query("token".to_string()));
+        let app = test::init_service(
+            App::new()
+                .app_data(verifier)
+                .wrap(middleware::from_fn(require_token::<TestClaim>))
+                .route(
+                    "/",
+                    web::get().to(|claim: web::ReqData<TestClaim>| async move {
+                        claim.into_inner().username
+                    }),
+                ),
+        )
+        .await;
+
+        let request = test::TestRequest::get()
+            .uri(&format!(
+                "/?token={}",
+                token(&signer, chrono::Duration::minutes(5))
+            ))
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(test::read_body(response).await, "user123");
+
+        let request = test::TestRequest::get().uri("/").to_request();
+        let response = test::try_call_service(&app, request).await;
+        let error = response.err().unwrap();
+        assert_eq!(
+            error.as_response_error().status_code(),
+            StatusCode::UNAUTHORIZED
+        );
+
+        let request = test::TestRequest::get()
+            .uri("/?token=not-a-token")
+            .to_request();
+        let response = test::try_call_service(&app, request).await;
+        let error = response.err().unwrap();
+        assert_eq!(
+            error.as_response_error().status_code(),
+            StatusCode::BAD_REQUEST
+        );
+    }
+}
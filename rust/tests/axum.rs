@@ -0,0 +1,173 @@
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::{header, Request, StatusCode};
+    use axum::response::Response;
+    use axum::{middleware, routing::get, Extension, Router};
+    use hmac_serialiser::axum::{require_token, Signed};
+    use hmac_serialiser::web::{TokenLocation, TokenVerifier};
+    use hmac_serialiser::{Algorithm, Encoder, HmacSigner, KeyInfo, Payload};
+    use serde::{Deserialize, Serialize};
+    use tower::ServiceExt as _;
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    struct TestClaim {
+        username: String,
+        exp: chrono::DateTime<chrono::Utc>,
+    }
+
+    impl Payload for TestClaim {
+        fn get_exp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+            Some(self.exp)
+        }
+    }
+
+    fn setup() -> HmacSigner {
+        let key_info = KeyInfo {
+            key: b"test_secret_key".to_vec(),
+            salt: b"salt".to_vec(),
+            info: vec![],
+        };
+        HmacSigner::new(key_info, Algorithm::SHA256, Encoder::Standard)
+    }
+
+    fn token(signer: &HmacSigner, exp: chrono::Duration) -> String {
+        signer.sign(&TestClaim {
+            username: "user123".to_string(),
+            exp: chrono::Utc::now() + exp,
+        })
+    }
+
+    fn app(location: TokenLocation) -> Router {
+        let verifier = TokenVerifier::new(setup()).with_location(location);
+        Router::new()
+            .route(
+                "/",
+                get(|Signed(claim): Signed<TestClaim>| async move { claim.username }),
+            )
+            .with_state(verifier)
+    }
+
+    async fn send(app: Router, request: Request<Body>) -> (StatusCode, String) {
+        let response: Response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_extractor_locations() {
+        let token = token(&setup(), chrono::Duration::minutes(5));
+        let requests = [
+            (
+                TokenLocation::Bearer,
+                Request::get("/")
+                    .header(header::AUTHORIZATION, format!("Bearer {}", token))
+                    .body(Body::empty())
+                    .unwrap(),
+            ),
+            (
+                TokenLocation::Header("x-api-token".to_string()),
+                Request::get("/")
+                    .header("x-api-token", &token)
+                    .body(Body::empty())
+                    .unwrap(),
+            ),
+            (
+                TokenLocation::Cookie("session".to_string()),
+                Request::get("/")
+                    .header(header::COOKIE, format!("theme=dark; session={}", token))
+                    .body(Body::empty())
+                    .unwrap(),
+            ),
+            (
+                TokenLocation::Query("token".to_string()),
+                Request::get(format!(
+                    "/?page=1&token={}",
+                    token
+                        .replace('+', "%2B")
+                        .replace('/', "%2F")
+                        .replace('=', "%3D")
+                ))
+                .body(Body::empty())
+                .unwrap(),
+            ),
+        ];
+        for (location, request) in requests {
+            assert_eq!(
+                send(app(location), request).await,
+                (StatusCode::OK, "user123".to_string())
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_extractor_rejections() {
+        let signer = setup();
+
+        let request = Request::get("/").body(Body::empty()).unwrap();
+        let (status, body) = send(app(TokenLocation::Bearer), request).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert!(body.contains("\"missing_token\""));
+
+        let expired = token(&signer, chrono::Duration::minutes(-5));
+        let request = Request::get("/")
+            .header(header::AUTHORIZATION, format!("Bearer {}", expired))
+            .body(Body::empty())
+            .unwrap();
+        let (status, body) = send(app(TokenLocation::Bearer), request).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert!(body.contains("\"token_expired\""));
+
+        let tampered = format!("A{}", token(&signer, chrono::Duration::minutes(5)));
+        let request = Request::get("/")
+            .header(header::AUTHORIZATION, format!("Bearer {}", tampered))
+            .body(Body::empty())
+            .unwrap();
+        let (status, body) = send(app(TokenLocation::Bearer), request).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert!(body.contains("\"invalid_token\""));
+
+        let request = Request::get("/")
+            .header(header::AUTHORIZATION, "Bearer not-a-token")
+            .body(Body::empty())
+            .unwrap();
+        let (status, body) = send(app(TokenLocation::Bearer), request).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.contains("\"invalid_input\""));
+        // the client's token is not echoed back
+        assert!(!body.contains("not-a-token"));
+    }
+
+    #[tokio::test]
+    async fn test_require_token_middleware() {
+        let signer = setup();
+        let verifier = TokenVerifier::new(signer.clone());
+        let app = Router::new()
+            .route(
+                "/",
+                get(|Extension(claim): Extension<TestClaim>| async move { claim.username }),
+            )
+            .layer(middleware::from_fn_with_state(
+                verifier,
+                require_token::<TestClaim>,
+            ));
+
+        let request = Request::get("/")
+            .header(
+                header::AUTHORIZATION,
+                format!("Bearer {}", token(&signer, chrono::Duration::minutes(5))),
+            )
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(
+            send(app.clone(), request).await,
+            (StatusCode::OK, "user123".to_string())
+        );
+
+        let request = Request::get("/").body(Body::empty()).unwrap();
+        assert_eq!(send(app, request).await.0, StatusCode::UNAUTHORIZED);
+    }
+}
@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod tests {
+    use hmac_serialiser::{
+        Algorithm, Encoder, Error, HmacSigner, KeyInfo, Payload, PayloadFormat,
+        DEFAULT_MAX_DECOMPRESSED_SIZE,
+    };
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct TestClaim {
+        username: String,
+        exp: chrono::DateTime<chrono::Utc>,
+    }
+
+    impl Payload for TestClaim {
+        fn get_exp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+            Some(self.exp)
+        }
+    }
+
+    fn claim(exp: chrono::DateTime<chrono::Utc>) -> TestClaim {
+        TestClaim {
+            username: "user123".to_string(),
+            exp,
+        }
+    }
+
+    #[test]
+    fn test_builder_matches_constructor() {
+        let key_info = KeyInfo {
+            key: b"test_secret_key".to_vec(),
+            salt: b"salt".to_vec(),
+            info: b"info".to_vec(),
+        };
+        let signer = HmacSigner::new(key_info, Algorithm::SHA512, Encoder::Standard);
+        let built = HmacSigner::builder()
+            .key(b"test_secret_key")
+            .salt(b"salt")
+            .info(b"info")
+            .algorithm(Algorithm::SHA512)
+            .encoder(Encoder::Standard)
+            .build()
+            .unwrap();
+
+        let token = signer.sign_str("user123");
+        assert_eq!(built.sign_str("user123"), token);
+        assert_eq!(built.unsign_str(&token).unwrap(), "user123");
+    }
+
+    #[test]
+    fn test_builder_options() {
+        let signer = HmacSigner::builder()
+            .key_info(KeyInfo {
+                key: b"test_secret_key".to_vec(),
+                salt: vec![],
+                info: vec![],
+            })
+            .separator("~")
+            .compression(DEFAULT_MAX_DECOMPRESSED_SIZE)
+            .format(PayloadFormat::Json)
+            .build()
+            .unwrap();
+
+        let payload = "a".repeat(1024);
+        let token = signer.sign_str(&payload);
+        assert!(token.starts_with('.'));
+        assert!(token.contains('~'));
+        assert_eq!(signer.unsign_str(&token).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_builder_leeway() {
+        let signer = HmacSigner::builder()
+            .key(b"test_secret_key")
+            .leeway(chrono::Duration::seconds(30))
+            .build()
+            .unwrap();
+
+        let token = signer.sign(&claim(chrono::Utc::now() - chrono::Duration::seconds(10)));
+        assert!(signer.unsign::<TestClaim>(&token).is_ok());
+
+        let token = signer.sign(&claim(chrono::Utc::now() - chrono::Duration::minutes(1)));
+        assert_eq!(
+            signer.unsign::<TestClaim>(&token).unwrap_err(),
+            Error::TokenExpired
+        );
+    }
+
+    #[test]
+    fn test_builder_validation() {
+        let result = HmacSigner::builder().build();
+        assert_eq!(result.unwrap_err(), Error::EmptyKey);
+
+        let result = HmacSigner::builder()
+            .key(b"short")
+            .min_key_length(16)
+            .build();
+        assert_eq!(result.unwrap_err(), Error::KeyTooShort(16));
+
+        let result = HmacSigner::builder()
+            .key(b"test_secret_key")
+            .encoder(Encoder::UrlSafe)
+            .separator("-")
+            .build();
+        assert_eq!(
+            result.unwrap_err(),
+            Error::InvalidSeparator("-".to_string())
+        );
+
+        let result = HmacSigner::builder()
+            .key(b"test_secret_key")
+            .leeway(chrono::Duration::seconds(-1))
+            .build();
+        assert!(matches!(result.unwrap_err(), Error::InvalidInput(_)));
+
+        let result = HmacSigner::builder()
+            .key(b"test_secret_key")
+            .leeway(chrono::Duration::days(365))
+            .build();
+        assert!(matches!(result.unwrap_err(), Error::InvalidInput(_)));
+    }
+}
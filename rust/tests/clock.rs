@@ -0,0 +1,246 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{claim, key_info, key_info_with_key, start, TestClaim};
+    use hmac_serialiser::interop::{CSharpTimedSigner, DEFAULT_SEPARATOR};
+    use hmac_serialiser::itsdangerous::UrlSafeTimedSerializer;
+    use hmac_serialiser::{
+        Algorithm, Encoder, EncryptedSigner, Error, FixedClock, HmacSigner, Keyring,
+        RotatingSigner, TimedSigner,
+    };
+
+    #[test]
+    fn test_fixed_clock() {
+        let clock = FixedClock::new(start());
+        let signer = HmacSigner::new(key_info(), Algorithm::SHA256, Encoder::UrlSafeNoPadding)
+            .with_clock(clock.clone());
+
+        // the token expired long ago according to the system clock
+        let token = signer.sign(&claim(start() + chrono::Duration::minutes(5)));
+        assert_eq!(
+            signer.unsign::<TestClaim>(&token).unwrap(),
+            claim(start() + chrono::Duration::minutes(5))
+        );
+
+        clock.advance(chrono::Duration::minutes(5));
+        assert!(signer.unsign::<TestClaim>(&token).is_ok());
+
+        clock.advance(chrono::Duration::seconds(1));
+        assert_eq!(
+            signer.unsign::<TestClaim>(&token).unwrap_err(),
+            Error::TokenExpired
+        );
+
+        clock.set(start());
+        assert!(signer.unsign::<TestClaim>(&token).is_ok());
+    }
+
+    #[test]
+    fn test_unsign_at() {
+        let signer = HmacSigner::new(key_info(), Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+        let token = signer.sign(&claim(start()));
+
+        assert!(signer.unsign_at::<TestClaim>(&token, start()).is_ok());
+        assert_eq!(
+            signer
+                .unsign_at::<TestClaim>(&token, start() + chrono::Duration::seconds(1))
+                .unwrap_err(),
+            Error::TokenExpired
+        );
+        assert_eq!(
+            signer.unsign::<TestClaim>(&token).unwrap_err(),
+            Error::TokenExpired
+        );
+
+        // the signature is still verified before the expiration time
+        let tampered = format!("{}A", token);
+        assert!(signer.unsign_at::<TestClaim>(&tampered, start()).is_err());
+    }
+
+    #[test]
+    fn test_builder_clock() {
+        let signer = HmacSigner::builder()
+            .key_info(key_info())
+            .clock(FixedClock::new(start()))
+            .build()
+            .unwrap();
+        let token = signer.sign(&claim(start()));
+        assert!(signer.unsign::<TestClaim>(&token).is_ok());
+    }
+
+    #[test]
+    fn test_timed_signer_clock() {
+        let clock = FixedClock::new(start());
+        let signer = TimedSigner::new(key_info(), Algorithm::SHA256, Encoder::UrlSafeNoPadding)
+            .with_clock(clock.clone());
+
+        let token = signer.sign(&"user123");
+        let (_, timestamp) = signer.unsign_with_timestamp::<String>(&token).unwrap();
+        assert_eq!(timestamp, start());
+
+        clock.advance(chrono::Duration::minutes(15));
+        assert!(signer
+            .unsign_with_max_age::<String>(&token, chrono::Duration::minutes(15))
+            .is_ok());
+
+        clock.advance(chrono::Duration::seconds(1));
+        assert_eq!(
+            signer
+                .unsign_with_max_age::<String>(&token, chrono::Duration::minutes(15))
+                .unwrap_err(),
+            Error::TokenExpired
+        );
+    }
+
+    #[test]
+    fn test_encrypted_signer_clock() {
+        let clock = FixedClock::new(start());
+        let signer = EncryptedSigner::new(key_info(), Algorithm::SHA256, Encoder::UrlSafeNoPadding)
+            .with_clock(clock.clone());
+
+        let token = signer.sign(&claim(start()));
+        assert!(signer.unsign::<TestClaim>(&token).is_ok());
+
+        clock.advance(chrono::Duration::seconds(1));
+        assert_eq!(
+            signer.unsign::<TestClaim>(&token).unwrap_err(),
+            Error::TokenExpired
+        );
+    }
+
+    #[test]
+    fn test_fixed_clock_out_of_range() {
+        let clock = FixedClock::new(start());
+        assert!(clock.try_advance(chrono::Duration::MAX).is_err());
+        assert_eq!(clock.now(), start());
+
+        clock.try_advance(chrono::Duration::seconds(1)).unwrap();
+        assert_eq!(clock.now(), start() + chrono::Duration::seconds(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "clock time is out of range")]
+    fn test_fixed_clock_advance_panics_when_out_of_range() {
+        FixedClock::new(start()).advance(chrono::Duration::MIN);
+    }
+
+    #[test]
+    fn test_rotating_signer_clock() {
+        let old_key = key_info_with_key(b"old_secret_key");
+        let clock = FixedClock::new(start());
+        let old_signer = HmacSigner::new(
+            old_key.clone(),
+            Algorithm::SHA256,
+            Encoder::UrlSafeNoPadding,
+        );
+        let signer = RotatingSigner::new(
+            key_info(),
+            vec![old_key],
+            Algorithm::SHA256,
+            Encoder::UrlSafeNoPadding,
+        )
+        .with_clock(clock.clone());
+
+        let token = signer.sign(&claim(start()));
+        let old_token = old_signer.sign(&claim(start()));
+        assert!(signer.unsign::<TestClaim>(&token).is_ok());
+        assert!(signer.unsign::<TestClaim>(&old_token).is_ok());
+
+        clock.advance(chrono::Duration::seconds(1));
+        for token in [token, old_token] {
+            assert_eq!(
+                signer.unsign::<TestClaim>(&token).unwrap_err(),
+                Error::TokenExpired
+            );
+        }
+    }
+
+    #[test]
+    fn test_keyring_clock() {
+        let clock = FixedClock::new(start());
+        let mut keyring = Keyring::new(
+            "v1",
+            key_info(),
+            Algorithm::SHA256,
+            Encoder::UrlSafeNoPadding,
+        )
+        .unwrap()
+        .with_clock(clock.clone());
+        let token = keyring.sign(&claim(start()));
+
+        // keys added after the clock was set use it as well
+        keyring
+            .add_key("v2", key_info_with_key(b"new_secret_key"))
+            .unwrap();
+        keyring.set_current("v2").unwrap();
+        let new_token = keyring.sign(&claim(start()));
+
+        assert!(keyring.unsign::<TestClaim>(&token).is_ok());
+        assert!(keyring.unsign::<TestClaim>(&new_token).is_ok());
+
+        clock.advance(chrono::Duration::seconds(1));
+        for token in [token, new_token] {
+            assert_eq!(
+                keyring.unsign::<TestClaim>(&token).unwrap_err(),
+                Error::TokenExpired
+            );
+        }
+    }
+
+    #[test]
+    fn test_csharp_timed_signer_clock() {
+        let clock = FixedClock::new(start());
+        let signer = CSharpTimedSigner::new(
+            key_info(),
+            chrono::Duration::minutes(15),
+            Algorithm::SHA256,
+            Encoder::UrlSafeNoPadding,
+            DEFAULT_SEPARATOR,
+        )
+        .unwrap()
+        .with_clock(clock.clone());
+
+        let token = signer.sign(&"user123");
+        assert_eq!(token, signer.sign_at(&"user123", start()));
+
+        clock.advance(chrono::Duration::minutes(15) - chrono::Duration::seconds(1));
+        assert_eq!(signer.unsign_str(&token).unwrap(), "user123");
+
+        // like the C# library, the token expires once its age reaches the maximum age
+        clock.advance(chrono::Duration::seconds(1));
+        assert_eq!(signer.unsign_str(&token).unwrap_err(), Error::TokenExpired);
+    }
+
+    #[test]
+    fn test_itsdangerous_timed_serializer_clock() {
+        let clock = FixedClock::new(start());
+        let serializer = UrlSafeTimedSerializer::flask_session(b"test_secret_key")
+            .unwrap()
+            .with_clock(clock.clone());
+
+        let token = serializer.dumps(&"user123");
+        let (_, timestamp) = serializer.loads_with_timestamp::<String>(&token).unwrap();
+        assert_eq!(timestamp, start());
+
+        let max_age = Some(chrono::Duration::minutes(15));
+        clock.advance(chrono::Duration::minutes(15));
+        assert_eq!(
+            serializer.loads::<String>(&token, max_age).unwrap(),
+            "user123"
+        );
+
+        clock.advance(chrono::Duration::seconds(1));
+        assert_eq!(
+            serializer.loads::<String>(&token, max_age).unwrap_err(),
+            Error::TokenExpired
+        );
+
+        // tokens signed in the future are rejected as well
+        clock.set(start() - chrono::Duration::seconds(1));
+        assert_eq!(
+            serializer.loads::<String>(&token, max_age).unwrap_err(),
+            Error::TokenExpired
+        );
+    }
+}
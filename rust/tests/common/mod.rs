@@ -0,0 +1,40 @@
+// fixtures shared by the time related tests, not every test file uses all of them
+#![allow(dead_code)]
+
+use hmac_serialiser::{KeyInfo, Payload};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct TestClaim {
+    pub username: String,
+    pub exp: chrono::DateTime<chrono::Utc>,
+}
+
+impl Payload for TestClaim {
+    fn get_exp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        Some(self.exp)
+    }
+}
+
+pub fn key_info() -> KeyInfo {
+    key_info_with_key(b"test_secret_key")
+}
+
+pub fn key_info_with_key(key: &[u8]) -> KeyInfo {
+    KeyInfo {
+        key: key.to_vec(),
+        salt: b"salt".to_vec(),
+        info: vec![],
+    }
+}
+
+pub fn start() -> chrono::DateTime<chrono::Utc> {
+    chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap()
+}
+
+pub fn claim(exp: chrono::DateTime<chrono::Utc>) -> TestClaim {
+    TestClaim {
+        username: "user123".to_string(),
+        exp,
+    }
+}
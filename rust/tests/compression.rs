@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+    use hmac_serialiser::{
+        Algorithm, Encoder, Error, HmacSigner, KeyInfo, Payload, DEFAULT_MAX_DECOMPRESSED_SIZE,
+        DELIM,
+    };
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Debug)]
+    struct TestPermissions {
+        permissions: Vec<String>,
+    }
+
+    impl Payload for TestPermissions {
+        fn get_exp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+            None
+        }
+    }
+
+    fn setup() -> HmacSigner {
+        let key_info = KeyInfo {
+            key: b"test_secret_key".to_vec(),
+            salt: b"test_salt".to_vec(),
+            info: vec![],
+        };
+        HmacSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding)
+    }
+
+    fn permissions(count: usize) -> TestPermissions {
+        TestPermissions {
+            permissions: (0..count)
+                .map(|i| format!("documents:{}:read", i))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_compressed_token() {
+        let signer = setup().with_compression(DEFAULT_MAX_DECOMPRESSED_SIZE);
+        let payload = permissions(100);
+
+        let token = signer.sign(&payload);
+        let uncompressed_token = setup().sign(&payload);
+        assert!(token.starts_with(DELIM));
+        assert!(token.len() < uncompressed_token.len() / 2);
+
+        let verified: TestPermissions = signer.unsign(&token).unwrap();
+        assert_eq!(verified.permissions, payload.permissions);
+
+        // uncompressed tokens are still accepted
+        let verified: TestPermissions = signer.unsign(&uncompressed_token).unwrap();
+        assert_eq!(verified.permissions, payload.permissions);
+    }
+
+    #[test]
+    fn test_small_payload_is_not_compressed() {
+        let signer = setup().with_compression(DEFAULT_MAX_DECOMPRESSED_SIZE);
+        let payload = permissions(1);
+
+        let token = signer.sign(&payload);
+        assert_eq!(token, setup().sign(&payload));
+    }
+
+    #[test]
+    fn test_decompressed_payload_too_large() {
+        let token = setup()
+            .with_compression(DEFAULT_MAX_DECOMPRESSED_SIZE)
+            .sign(&permissions(1000));
+
+        let signer = setup().with_compression(1024);
+        let result = signer.unsign::<TestPermissions>(&token);
+        assert_eq!(result.unwrap_err(), Error::PayloadTooLarge(1024));
+    }
+
+    #[test]
+    fn test_compressed_token_without_compression() {
+        let token = setup()
+            .with_compression(DEFAULT_MAX_DECOMPRESSED_SIZE)
+            .sign(&permissions(100));
+
+        let result = setup().unsign::<TestPermissions>(&token);
+        assert_eq!(result.unwrap_err(), Error::PayloadDecodeError);
+    }
+}
@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod tests {
+    use cookie::{Cookie, CookieJar, SameSite};
+    use hmac_serialiser::cookie::CookieSigner;
+    use hmac_serialiser::{Algorithm, Encoder, Error, FixedClock, HmacSigner, KeyInfo, Payload};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Session {
+        username: String,
+        exp: Option<chrono::DateTime<chrono::Utc>>,
+    }
+
+    impl Payload for Session {
+        fn get_exp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+            self.exp
+        }
+    }
+
+    fn start() -> chrono::DateTime<chrono::Utc> {
+        chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap()
+    }
+
+    fn setup() -> CookieSigner {
+        let key_info = KeyInfo {
+            key: b"test_secret_key".to_vec(),
+            salt: b"salt".to_vec(),
+            info: vec![],
+        };
+        let signer = HmacSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding)
+            .with_clock(FixedClock::new(start()));
+        CookieSigner::new(signer)
+    }
+
+    fn session(exp: Option<chrono::DateTime<chrono::Utc>>) -> Session {
+        Session {
+            username: "user123".to_string(),
+            exp,
+        }
+    }
+
+    #[test]
+    fn test_cookie_attributes() {
+        let signer = setup();
+        let exp = start() + chrono::Duration::hours(1);
+        let cookie = signer.cookie("session", &session(Some(exp))).unwrap();
+
+        assert_eq!(cookie.name(), "session");
+        assert_eq!(cookie.http_only(), Some(true));
+        assert_eq!(cookie.secure(), Some(true));
+        assert_eq!(cookie.same_site(), Some(SameSite::Lax));
+        assert_eq!(cookie.path(), Some("/"));
+        assert_eq!(cookie.max_age(), Some(cookie::time::Duration::hours(1)));
+        assert_eq!(
+            cookie.expires_datetime().unwrap().unix_timestamp(),
+            exp.timestamp()
+        );
+        assert_eq!(
+            signer.verify::<Session>(&cookie).unwrap(),
+            session(Some(exp))
+        );
+
+        let cookie = signer.cookie("session", &session(None)).unwrap();
+        assert_eq!(cookie.max_age(), None);
+        assert_eq!(cookie.expires(), None);
+    }
+
+    #[test]
+    fn test_custom_attributes() {
+        let signer = setup()
+            .with_http_only(false)
+            .with_secure(false)
+            .with_same_site(SameSite::Strict)
+            .with_path("/app")
+            .with_domain("example.com");
+        let cookie = signer.cookie("session", &session(None)).unwrap();
+
+        assert_eq!(cookie.http_only(), Some(false));
+        assert_eq!(cookie.secure(), Some(false));
+        assert_eq!(cookie.same_site(), Some(SameSite::Strict));
+        assert_eq!(cookie.path(), Some("/app"));
+        assert_eq!(cookie.domain(), Some("example.com"));
+    }
+
+    #[test]
+    fn test_signed_cookie_jar() {
+        let signer = setup();
+        let mut jar = CookieJar::new();
+
+        let mut signed = signer.jar(&mut jar);
+        signed.add("session", &session(None)).unwrap();
+        assert_eq!(
+            signed.get::<Session>("session").unwrap().unwrap(),
+            session(None)
+        );
+        assert!(signed.get::<Session>("other").is_none());
+        assert_eq!(jar.delta().count(), 1);
+
+        // a cookie tampered with by the client is rejected
+        let value = format!("{}A", jar.get("session").unwrap().value());
+        jar.add_original(Cookie::new("tampered", value));
+        assert_eq!(
+            signer.jar(&mut jar).get::<Session>("tampered").unwrap(),
+            Err(Error::InvalidToken)
+        );
+
+        signer.jar(&mut jar).remove("session");
+        assert!(jar.get("session").is_none());
+    }
+
+    #[test]
+    fn test_expired_cookie() {
+        let signer = setup();
+        let cookie = signer
+            .cookie(
+                "session",
+                &session(Some(start() - chrono::Duration::minutes(1))),
+            )
+            .unwrap();
+        assert_eq!(cookie.max_age(), Some(cookie::time::Duration::ZERO));
+        assert_eq!(
+            signer.verify::<Session>(&cookie).unwrap_err(),
+            Error::TokenExpired
+        );
+    }
+
+    #[test]
+    fn test_oversized_cookie() {
+        let signer = setup().with_max_size(64);
+        let result = signer.cookie("session", &session(None));
+        assert_eq!(result.unwrap_err(), Error::CookieTooLarge(64));
+
+        let cookie = Cookie::new("session", "a".repeat(128));
+        assert_eq!(
+            signer.verify::<Session>(&cookie).unwrap_err(),
+            Error::CookieTooLarge(64)
+        );
+    }
+}
@@ -0,0 +1,189 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use hmac_serialiser::interop::{CSharpSigner, CSharpTimedSigner, DEFAULT_SALT};
+    use hmac_serialiser::{Algorithm, Encoder, Error, KeyInfo};
+    use serde::Deserialize;
+
+    // shared with the C# test suite, generated by test-vectors/generate_hmac_serialiser.py
+    const TEST_VECTORS: &str = include_str!("../../test-vectors/hmac_serialiser.json");
+
+    #[derive(Deserialize, Debug)]
+    struct TestVectors {
+        vectors: Vec<TestVector>,
+    }
+
+    #[derive(Deserialize, Debug)]
+    struct TestVector {
+        name: String,
+        serialiser: String,
+        algorithm: String,
+        key: String,
+        salt: Option<String>,
+        info: Option<String>,
+        sep: String,
+        payload: serde_json::Value,
+        timestamp: Option<i64>,
+        max_age: Option<i64>,
+        token: String,
+    }
+
+    impl TestVector {
+        fn key_info(&self) -> KeyInfo {
+            KeyInfo {
+                key: self.key.as_bytes().to_vec(),
+                salt: self
+                    .salt
+                    .as_ref()
+                    .map_or(DEFAULT_SALT.to_vec(), |salt| salt.as_bytes().to_vec()),
+                info: self
+                    .info
+                    .as_ref()
+                    .map_or(vec![], |info| info.as_bytes().to_vec()),
+            }
+        }
+
+        fn algorithm(&self) -> Algorithm {
+            match self.algorithm.as_str() {
+                "SHA1" => Algorithm::SHA1,
+                "SHA256" => Algorithm::SHA256,
+                "SHA384" => Algorithm::SHA384,
+                "SHA512" => Algorithm::SHA512,
+                algorithm => panic!("unknown algorithm: {}", algorithm),
+            }
+        }
+
+        fn encoder(&self) -> Encoder {
+            if self.serialiser.contains("urlsafe") {
+                Encoder::UrlSafeNoPadding
+            } else {
+                Encoder::StandardNoPadding
+            }
+        }
+    }
+
+    fn load_vectors() -> Vec<TestVector> {
+        let vectors: TestVectors = serde_json::from_str(TEST_VECTORS).unwrap();
+        assert!(!vectors.vectors.is_empty());
+        vectors.vectors
+    }
+
+    #[test]
+    fn test_sign_matches_vectors() {
+        for vector in load_vectors() {
+            let token = match vector.timestamp {
+                None => CSharpSigner::new(
+                    vector.key_info(),
+                    vector.algorithm(),
+                    vector.encoder(),
+                    &vector.sep,
+                )
+                .unwrap()
+                .sign(&vector.payload),
+                Some(timestamp) => CSharpTimedSigner::new(
+                    vector.key_info(),
+                    Duration::seconds(vector.max_age.unwrap()),
+                    vector.algorithm(),
+                    vector.encoder(),
+                    &vector.sep,
+                )
+                .unwrap()
+                .sign_at(
+                    &vector.payload,
+                    chrono::DateTime::from_timestamp(timestamp, 0).unwrap(),
+                ),
+            };
+            assert_eq!(token, vector.token, "vector: {}", vector.name);
+        }
+    }
+
+    #[test]
+    fn test_unsign_matches_vectors() {
+        for vector in load_vectors() {
+            let payload: serde_json::Value = match vector.timestamp {
+                None => CSharpSigner::new(
+                    vector.key_info(),
+                    vector.algorithm(),
+                    vector.encoder(),
+                    &vector.sep,
+                )
+                .unwrap()
+                .unsign(&vector.token),
+                Some(_) => CSharpTimedSigner::new(
+                    vector.key_info(),
+                    Duration::seconds(vector.max_age.unwrap()),
+                    vector.algorithm(),
+                    vector.encoder(),
+                    &vector.sep,
+                )
+                .unwrap()
+                .unsign(&vector.token),
+            }
+            .unwrap();
+            assert_eq!(payload, vector.payload, "vector: {}", vector.name);
+        }
+    }
+
+    #[test]
+    fn test_unsign_str() {
+        let key_info = KeyInfo {
+            key: b"secretkey".to_vec(),
+            salt: b"randomsalt".to_vec(),
+            info: b"testing".to_vec(),
+        };
+        let signer =
+            CSharpSigner::new(key_info, Algorithm::SHA256, Encoder::Standard, "~").unwrap();
+        let token = signer.sign(&"héllo wörld ✓");
+        assert_eq!(signer.unsign_str(&token).unwrap(), "héllo wörld ✓");
+        assert_eq!(signer.unsign::<String>(&token).unwrap(), "héllo wörld ✓");
+    }
+
+    #[test]
+    fn test_expired_timed_token() {
+        let key_info = KeyInfo {
+            key: b"secretkey".to_vec(),
+            salt: DEFAULT_SALT.to_vec(),
+            info: vec![],
+        };
+        let signer = CSharpTimedSigner::new(
+            key_info,
+            Duration::seconds(3600),
+            Algorithm::SHA1,
+            Encoder::UrlSafeNoPadding,
+            ".",
+        )
+        .unwrap();
+
+        let token = signer.sign_at(&"data", Utc::now() - Duration::seconds(3600));
+        assert_eq!(signer.unsign_str(&token), Err(Error::TokenExpired));
+
+        let token = signer.sign(&"data");
+        assert_eq!(signer.unsign_str(&token).unwrap(), "data");
+    }
+
+    #[test]
+    fn test_invalid_separator() {
+        for (sep, encoder) in [
+            ("", Encoder::UrlSafeNoPadding),
+            ("a", Encoder::UrlSafeNoPadding),
+            ("-", Encoder::UrlSafeNoPadding),
+            ("+", Encoder::StandardNoPadding),
+            ("=", Encoder::Standard),
+        ] {
+            let result = CSharpSigner::new(
+                KeyInfo {
+                    key: b"secretkey".to_vec(),
+                    salt: vec![],
+                    info: vec![],
+                },
+                Algorithm::SHA256,
+                encoder,
+                sep,
+            );
+            assert_eq!(
+                result.unwrap_err(),
+                Error::InvalidSeparator(sep.to_string())
+            );
+        }
+    }
+}
@@ -0,0 +1,129 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use hmac_serialiser::{
+        Algorithm, Encoder, EncryptedSigner, Error, HmacSigner, KeyInfo, Payload, DELIM,
+    };
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Debug)]
+    struct TestClaim {
+        #[serde(with = "chrono::serde::ts_seconds")]
+        exp: chrono::DateTime<Utc>,
+        data: String,
+    }
+
+    impl Payload for TestClaim {
+        fn get_exp(&self) -> Option<chrono::DateTime<Utc>> {
+            Some(self.exp)
+        }
+    }
+
+    #[derive(Serialize, Deserialize, Debug)]
+    struct TestData {
+        data: String,
+    }
+
+    impl Payload for TestData {
+        fn get_exp(&self) -> Option<chrono::DateTime<Utc>> {
+            None
+        }
+    }
+
+    fn key_info() -> KeyInfo {
+        KeyInfo {
+            key: b"test_secret_key".to_vec(),
+            salt: b"test_salt".to_vec(),
+            info: b"encrypted".to_vec(),
+        }
+    }
+
+    fn setup() -> EncryptedSigner {
+        EncryptedSigner::new(key_info(), Algorithm::SHA256, Encoder::UrlSafeNoPadding)
+    }
+
+    #[test]
+    fn test_sign_and_unsign_encrypted_token() {
+        let signer = setup();
+        let claim = TestClaim {
+            exp: Utc::now() + Duration::hours(1),
+            data: "secret@example.com".to_string(),
+        };
+
+        let token = signer.sign(&claim);
+        assert_eq!(token.split(DELIM).count(), 2);
+        assert!(!token.contains("secret"));
+
+        // a random nonce is used for every token
+        assert_ne!(token, signer.sign(&claim));
+
+        let verified_claim: TestClaim = signer.unsign(&token).unwrap();
+        assert_eq!(verified_claim.data, claim.data);
+    }
+
+    #[test]
+    fn test_unsign_token_from_other_backend() {
+        // signed with the same key info using the rust_crypto backend
+        let token = "PgWdflfIwyubd4HqjEhnLIw295gAQlAYW9qeW9OJPCaI20K2zRh4bgSpPtG-v5fwmXYkysvkt6Em.691F11X57TnwjrzQsnuqCNkrwo33DrTm5-KTGBLnZd8";
+        let data: TestData = setup().unsign(token).unwrap();
+        assert_eq!(data.data, "secret@example.com");
+    }
+
+    #[test]
+    fn test_unsign_expired_encrypted_token() {
+        let signer = setup();
+        let claim = TestClaim {
+            exp: Utc::now() - Duration::hours(1),
+            data: "test_data".to_string(),
+        };
+        let token = signer.sign(&claim);
+        let result = signer.unsign::<TestClaim>(&token);
+        assert_eq!(result.unwrap_err(), Error::TokenExpired);
+    }
+
+    #[test]
+    fn test_unsign_tampered_encrypted_token() {
+        let signer = setup();
+        let token = signer.sign(&TestData {
+            data: "test_data".to_string(),
+        });
+
+        let (payload, signature) = token.split_once(DELIM).unwrap();
+        let first = if payload.starts_with('A') { 'B' } else { 'A' };
+        let tampered_token = format!("{}{}{}{}", first, &payload[1..], DELIM, signature);
+        let result = signer.unsign::<TestData>(&tampered_token);
+        assert_eq!(result.unwrap_err(), Error::InvalidToken);
+
+        let other_signer = EncryptedSigner::new(
+            KeyInfo {
+                key: b"other_secret_key".to_vec(),
+                salt: b"test_salt".to_vec(),
+                info: b"encrypted".to_vec(),
+            },
+            Algorithm::SHA256,
+            Encoder::UrlSafeNoPadding,
+        );
+        let result = other_signer.unsign::<TestData>(&token);
+        assert_eq!(result.unwrap_err(), Error::InvalidToken);
+    }
+
+    #[test]
+    fn test_plain_signer_cannot_unsign_encrypted_token() {
+        let token = setup().sign(&TestData {
+            data: "test_data".to_string(),
+        });
+        let signer = HmacSigner::new(key_info(), Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+        let result = signer.unsign::<TestData>(&token);
+        assert_eq!(result.unwrap_err(), Error::InvalidToken);
+    }
+
+    #[test]
+    fn test_encrypted_signer_rejects_invalid_key() {
+        let result = EncryptedSigner::try_new(
+            KeyInfo::default(),
+            Algorithm::SHA256,
+            Encoder::UrlSafeNoPadding,
+        );
+        assert_eq!(result.unwrap_err(), Error::EmptyKey);
+    }
+}
@@ -1,7 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use hmac_serialiser::{hkdf::HkdfWrapper, Algorithm};
-    use rand;
+    use hmac_serialiser::{hkdf::HkdfWrapper, Algorithm, Error};
     use rand::Rng as _;
 
     pub fn get_random_bytes(length: usize) -> Vec<u8> {
@@ -22,7 +21,7 @@ mod tests {
         let algo = Algorithm::SHA1;
         let algo_output_length = algo.output_length();
         let hkdf = HkdfWrapper::new(algo);
-        let okm = hkdf.expand(ikm, salt, info);
+        let okm = hkdf.expand(ikm, salt, info).unwrap();
 
         println!("sha1 okm: {}", bytes_to_hex(&okm));
         assert_eq!(okm.len(), algo_output_length);
@@ -36,7 +35,7 @@ mod tests {
         let algo = Algorithm::SHA256;
         let algo_output_length = algo.output_length();
         let hkdf = HkdfWrapper::new(algo);
-        let okm = hkdf.expand(ikm, &salt, info);
+        let okm = hkdf.expand(ikm, &salt, info).unwrap();
 
         println!("sha256 okm: {}", bytes_to_hex(&okm));
         assert_eq!(okm.len(), algo_output_length);
@@ -50,7 +49,7 @@ mod tests {
         let algo = Algorithm::SHA384;
         let algo_output_length = algo.output_length();
         let hkdf = HkdfWrapper::new(algo);
-        let okm = hkdf.expand(ikm.as_ref(), salt, info);
+        let okm = hkdf.expand(ikm.as_ref(), salt, info).unwrap();
 
         println!("sha384 okm: {}", bytes_to_hex(&okm));
         assert_eq!(okm.len(), algo_output_length);
@@ -64,7 +63,7 @@ mod tests {
         let algo = Algorithm::SHA512;
         let algo_output_length = algo.output_length();
         let hkdf = HkdfWrapper::new(algo);
-        let okm = hkdf.expand(ikm, salt, info);
+        let okm = hkdf.expand(ikm, salt, info).unwrap();
 
         println!("sha512 okm: {}", bytes_to_hex(&okm));
         assert_eq!(okm.len(), algo_output_length);
@@ -78,9 +77,24 @@ mod tests {
         let algo = Algorithm::SHA256;
         let algo_output_length = algo.output_length();
         let hkdf = HkdfWrapper::new(algo);
-        let okm = hkdf.expand(ikm, salt, info);
+        let okm = hkdf.expand(ikm, salt, info).unwrap();
 
         println!("sha256 okm: {}", bytes_to_hex(&okm));
         assert_eq!(okm.len(), algo_output_length);
     }
+
+    #[test]
+    fn test_hkdf_expand_invalid_length() {
+        let algo = Algorithm::SHA256;
+        let max_length = 255 * algo.output_length();
+        let hkdf = HkdfWrapper::new(algo);
+
+        let okm = hkdf
+            .expand_to_length(b"jason", b"", b"", max_length)
+            .unwrap();
+        assert_eq!(okm.len(), max_length);
+
+        let result = hkdf.expand_to_length(b"jason", b"", b"", max_length + 1);
+        assert_eq!(result.unwrap_err(), Error::HkdfExpandError);
+    }
 }
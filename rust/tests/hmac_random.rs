@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod tests {
     use chrono::{Duration, Utc};
+    use hmac_serialiser::hkdf::HkdfWrapper;
+    use hmac_serialiser::itsdangerous::{KeyDerivation, Signer};
     use hmac_serialiser::{Algorithm, Encoder, Error, HmacSigner, KeyInfo, Payload, DELIM};
     use serde::{Deserialize, Serialize};
 
@@ -51,7 +53,7 @@ mod tests {
             Algorithm::SHA256,
             Encoder::UrlSafe,
         );
-        match signer.unsign::<TestClaim>(&data) {
+        match signer.unsign::<TestClaim>(data) {
             Ok(_) => panic!("Expected error"),
             Err(e) => assert_eq!(e, expected_error),
         };
@@ -158,4 +160,232 @@ mod tests {
 
         assert!(matches!(result, Err(Error::InvalidSignature)));
     }
+
+    #[test]
+    fn test_unsign_authentic_but_malformed_payload() {
+        let signer = setup(vec![], vec![], Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+
+        // signs raw values with the same expanded key as another tool would
+        let expanded_key = HkdfWrapper::new(Algorithm::SHA256)
+            .expand(b"test_secret_key", b"", b"")
+            .unwrap();
+        let other_tool = Signer::new(
+            &expanded_key,
+            b"",
+            KeyDerivation::None,
+            Algorithm::SHA256,
+            ".",
+        )
+        .unwrap();
+
+        let token = other_tool.sign("not+base64");
+        let result = signer.unsign::<TestClaim>(&token);
+        assert_eq!(result.unwrap_err(), Error::PayloadDecodeError);
+
+        // 0xff is never valid utf-8
+        let token = other_tool.sign("_w");
+        let result = signer.unsign::<TestClaim>(&token);
+        assert_eq!(result.unwrap_err(), Error::PayloadUtf8Error);
+    }
+
+    #[derive(Serialize, Debug)]
+    struct UnserialisableClaim {
+        scores: std::collections::HashMap<(u8, u8), u8>,
+    }
+
+    impl Payload for UnserialisableClaim {
+        fn get_exp(&self) -> Option<chrono::DateTime<Utc>> {
+            None
+        }
+    }
+
+    #[test]
+    fn test_try_sign_serialisation_error() {
+        let signer = setup(vec![], vec![], Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+        let claim = TestClaim {
+            exp: Utc::now() + Duration::hours(1),
+            data: "test_data".to_string(),
+        };
+        let token = signer.try_sign(&claim).unwrap();
+        assert_eq!(token, signer.sign(&claim));
+
+        let claim = UnserialisableClaim {
+            scores: [((1, 2), 3)].into(),
+        };
+        match signer.try_sign(&claim) {
+            Err(Error::SerialisationError(msg)) => assert_eq!(msg, "key must be a string"),
+            result => panic!("Expected serialisation error, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_sign_and_unsign_bytes() {
+        let signer = setup(vec![], vec![], Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+        let payload = [0xffu8, 0x00, 0x7f, 0x80];
+
+        let token = signer.sign_bytes(&payload);
+        assert_eq!(signer.unsign_bytes(&token).unwrap(), payload);
+
+        // not valid utf-8
+        assert_eq!(
+            signer.unsign_str(&token).unwrap_err(),
+            Error::PayloadUtf8Error
+        );
+
+        let tampered_token = token.replacen(&token[..1], "A", 1);
+        assert_eq!(
+            signer.unsign_bytes(&tampered_token).unwrap_err(),
+            Error::InvalidToken
+        );
+    }
+
+    #[test]
+    fn test_sign_and_unsign_empty_payload() {
+        let signer = setup(vec![], vec![], Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+
+        let token = signer.sign_bytes(&[]);
+        assert!(token.starts_with(DELIM));
+        assert_eq!(signer.unsign_bytes(&token).unwrap(), Vec::<u8>::new());
+
+        let token = signer.sign_str("");
+        assert_eq!(signer.unsign_str(&token).unwrap(), "");
+
+        // an empty payload is not valid JSON
+        assert_eq!(
+            signer.unsign::<TestClaim>(&token).unwrap_err(),
+            Error::InvalidPayload
+        );
+
+        let other = setup(
+            b"other".to_vec(),
+            vec![],
+            Algorithm::SHA256,
+            Encoder::UrlSafeNoPadding,
+        );
+        assert_eq!(
+            signer.unsign_bytes(&other.sign_bytes(&[])).unwrap_err(),
+            Error::InvalidToken
+        );
+    }
+
+    #[test]
+    fn test_sign_and_unsign_str() {
+        let signer = setup(vec![], vec![], Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+        let token = signer.sign_str("user123");
+
+        // the string is signed without JSON quotes
+        assert_eq!(token.split(DELIM).next().unwrap(), "dXNlcjEyMw");
+        assert_eq!(signer.unsign_str(&token).unwrap(), "user123");
+    }
+
+    #[test]
+    fn test_sign_and_verify_detached() {
+        let signer = setup(
+            b"webhook".to_vec(),
+            vec![],
+            Algorithm::SHA512,
+            Encoder::Standard,
+        );
+        let body = br#"{"event":"payment.succeeded","amount":1000}"#;
+
+        let signature = signer.sign_detached(body);
+        assert!(!signature.contains(DELIM));
+        assert_eq!(signature, signer.sign_detached(body));
+        assert_eq!(signer.verify_detached(body, &signature), Ok(()));
+
+        let tampered_body = br#"{"event":"payment.succeeded","amount":9000}"#;
+        assert_eq!(
+            signer.verify_detached(tampered_body, &signature),
+            Err(Error::InvalidToken)
+        );
+        assert_eq!(
+            signer.verify_detached(body, "not a signature!"),
+            Err(Error::InvalidSignature)
+        );
+
+        let other_signer = setup(vec![], vec![], Algorithm::SHA512, Encoder::Standard);
+        assert_eq!(
+            other_signer.verify_detached(body, &signature),
+            Err(Error::InvalidToken)
+        );
+    }
+
+    #[test]
+    fn test_detached_signature_is_not_a_token_signature() {
+        let signer = setup(vec![], vec![], Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+
+        // {"user":"admin"}
+        let encoded_payload = "eyJ1c2VyIjoiYWRtaW4ifQ";
+        let signature = signer.sign_detached(encoded_payload.as_bytes());
+        let forged_token = format!("{}{}{}", encoded_payload, DELIM, signature);
+        assert_eq!(
+            signer.unsign_str(&forged_token).unwrap_err(),
+            Error::InvalidToken
+        );
+
+        // likewise, a token's signature does not verify the encoded payload as a detached signature
+        let token = signer.sign_str(r#"{"user":"admin"}"#);
+        let (encoded_payload, signature) = token.split_once(DELIM).unwrap();
+        assert_eq!(encoded_payload, "eyJ1c2VyIjoiYWRtaW4ifQ");
+        assert_eq!(
+            signer.verify_detached(encoded_payload.as_bytes(), signature),
+            Err(Error::InvalidToken)
+        );
+    }
+
+    #[test]
+    fn test_custom_separator() {
+        let key_info = KeyInfo {
+            key: b"test_secret_key".to_vec(),
+            salt: vec![],
+            info: vec![],
+        };
+        let claim = TestClaim {
+            exp: Utc::now() + Duration::hours(1),
+            data: "test_data".to_string(),
+        };
+        let default_token =
+            setup(vec![], vec![], Algorithm::SHA256, Encoder::UrlSafeNoPadding).sign(&claim);
+
+        for sep in [":", "~", "::"] {
+            let signer = HmacSigner::try_new_with_separator(
+                key_info.clone(),
+                Algorithm::SHA256,
+                Encoder::UrlSafeNoPadding,
+                sep,
+            )
+            .unwrap();
+
+            let token = signer.sign(&claim);
+            assert!(!token.contains(DELIM));
+            assert_eq!(token.replace(sep, "."), default_token);
+
+            let verified_claim: TestClaim = signer.unsign(&token).unwrap();
+            assert_eq!(verified_claim.data, claim.data);
+
+            let result = signer.unsign::<TestClaim>(&default_token);
+            assert_eq!(
+                result.unwrap_err(),
+                Error::InvalidInput(default_token.clone())
+            );
+        }
+
+        for (sep, encoder) in [
+            ("", Encoder::UrlSafeNoPadding),
+            ("_", Encoder::UrlSafeNoPadding),
+            ("/", Encoder::Standard),
+            ("a~", Encoder::StandardNoPadding),
+        ] {
+            let result = HmacSigner::try_new_with_separator(
+                key_info.clone(),
+                Algorithm::SHA256,
+                encoder,
+                sep,
+            );
+            assert_eq!(
+                result.unwrap_err(),
+                Error::InvalidSeparator(sep.to_string())
+            );
+        }
+    }
 }
@@ -0,0 +1,217 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use hmac_serialiser::itsdangerous::{
+        KeyDerivation, Serializer, Signer, TimestampSigner, UrlSafeSerializer,
+        UrlSafeTimedSerializer, DEFAULT_SEPARATOR, SERIALIZER_SALT,
+    };
+    use hmac_serialiser::{Algorithm, Error};
+    use serde::Deserialize;
+
+    // generated by test-vectors/generate_itsdangerous.py using Python's ItsDangerous and Flask
+    const TEST_VECTORS: &str = include_str!("../../test-vectors/itsdangerous.json");
+
+    #[derive(Deserialize, Debug)]
+    struct TestVectors {
+        vectors: Vec<TestVector>,
+    }
+
+    #[derive(Deserialize, Debug)]
+    struct TestVector {
+        kind: String,
+        algorithm: String,
+        key_derivation: String,
+        secret_key: String,
+        salt: String,
+        sep: String,
+        payload: serde_json::Value,
+        timestamp: Option<i64>,
+        token: String,
+    }
+
+    impl TestVector {
+        fn signer(&self) -> Signer {
+            let algo = match self.algorithm.as_str() {
+                "SHA1" => Algorithm::SHA1,
+                "SHA256" => Algorithm::SHA256,
+                "SHA384" => Algorithm::SHA384,
+                "SHA512" => Algorithm::SHA512,
+                algorithm => panic!("unknown algorithm: {}", algorithm),
+            };
+            let key_derivation = match self.key_derivation.as_str() {
+                "concat" => KeyDerivation::Concat,
+                "django-concat" => KeyDerivation::DjangoConcat,
+                "hmac" => KeyDerivation::Hmac,
+                "none" => KeyDerivation::None,
+                key_derivation => panic!("unknown key derivation: {}", key_derivation),
+            };
+            Signer::new(
+                self.secret_key.as_bytes(),
+                self.salt.as_bytes(),
+                key_derivation,
+                algo,
+                &self.sep,
+            )
+            .unwrap()
+        }
+
+        fn timestamp(&self) -> chrono::DateTime<Utc> {
+            chrono::DateTime::from_timestamp(self.timestamp.unwrap(), 0).unwrap()
+        }
+    }
+
+    fn load_vectors() -> Vec<TestVector> {
+        let vectors: TestVectors = serde_json::from_str(TEST_VECTORS).unwrap();
+        assert!(!vectors.vectors.is_empty());
+        vectors.vectors
+    }
+
+    #[test]
+    fn test_loads_matches_vectors() {
+        for vector in load_vectors() {
+            let signer = vector.signer();
+            let payload: serde_json::Value = match vector.kind.as_str() {
+                "signer" => signer.unsign(&vector.token).unwrap().into(),
+                "timestamp-signer" => {
+                    let (value, timestamp) = TimestampSigner::new(signer)
+                        .unsign_with_timestamp(&vector.token)
+                        .unwrap();
+                    assert_eq!(timestamp, vector.timestamp());
+                    value.into()
+                }
+                "serializer" => Serializer::new(signer).loads(&vector.token).unwrap(),
+                "url-safe-serializer" => {
+                    UrlSafeSerializer::new(signer).loads(&vector.token).unwrap()
+                }
+                "url-safe-timed-serializer" | "flask-session" => {
+                    let (payload, timestamp) =
+                        UrlSafeTimedSerializer::new(TimestampSigner::new(signer))
+                            .loads_with_timestamp(&vector.token)
+                            .unwrap();
+                    assert_eq!(timestamp, vector.timestamp());
+                    payload
+                }
+                kind => panic!("unknown kind: {}", kind),
+            };
+            assert_eq!(payload, vector.payload, "vector: {:?}", vector);
+        }
+    }
+
+    #[test]
+    fn test_dumps_matches_vectors() {
+        for vector in load_vectors() {
+            let signer = vector.signer();
+            let payload = &vector.payload;
+            let token = match vector.kind.as_str() {
+                "signer" => signer.sign(payload.as_str().unwrap()),
+                "timestamp-signer" => TimestampSigner::new(signer)
+                    .sign_at(payload.as_str().unwrap(), vector.timestamp()),
+                "serializer" => Serializer::new(signer).dumps(payload),
+                "url-safe-serializer" => UrlSafeSerializer::new(signer).dumps(payload),
+                "url-safe-timed-serializer" => {
+                    UrlSafeTimedSerializer::new(TimestampSigner::new(signer))
+                        .dumps_at(payload, vector.timestamp())
+                }
+                "flask-session" => {
+                    UrlSafeTimedSerializer::flask_session(vector.secret_key.as_bytes())
+                        .unwrap()
+                        .dumps_at(payload, vector.timestamp())
+                }
+                kind => panic!("unknown kind: {}", kind),
+            };
+
+            // the deflate implementation is not zlib's, so compressed payloads
+            // are not byte for byte identical even though ItsDangerous accepts them
+            if vector.token.starts_with('.') {
+                assert!(token.starts_with('.'), "vector: {:?}", vector);
+                assert!(token.len() < serde_json::to_string(payload).unwrap().len());
+                continue;
+            }
+            assert_eq!(token, vector.token, "vector: {:?}", vector);
+        }
+    }
+
+    #[test]
+    fn test_flask_session() {
+        let serializer = UrlSafeTimedSerializer::flask_session(b"secretkey").unwrap();
+        let session = serde_json::json!({ "user_id": 1 });
+        let cookie = serializer.dumps(&session);
+
+        let loaded: serde_json::Value =
+            serializer.loads(&cookie, Some(Duration::days(31))).unwrap();
+        assert_eq!(loaded, session);
+
+        let other = UrlSafeTimedSerializer::flask_session(b"otherkey").unwrap();
+        let result: Result<serde_json::Value, Error> = other.loads(&cookie, None);
+        assert_eq!(result, Err(Error::InvalidToken));
+    }
+
+    #[test]
+    fn test_expired_timestamp() {
+        let signer = Signer::new(
+            b"secretkey",
+            SERIALIZER_SALT,
+            KeyDerivation::default(),
+            Algorithm::SHA1,
+            DEFAULT_SEPARATOR,
+        )
+        .unwrap();
+        let serializer = UrlSafeTimedSerializer::new(TimestampSigner::new(signer));
+
+        let token = serializer.dumps_at(&"data", Utc::now() - Duration::hours(2));
+        let result: Result<String, Error> = serializer.loads(&token, Some(Duration::hours(1)));
+        assert_eq!(result, Err(Error::TokenExpired));
+
+        let token = serializer.dumps_at(&"data", Utc::now() + Duration::hours(2));
+        let result: Result<String, Error> = serializer.loads(&token, Some(Duration::hours(1)));
+        assert_eq!(result, Err(Error::TokenExpired));
+
+        let loaded: String = serializer.loads(&token, None).unwrap();
+        assert_eq!(loaded, "data");
+    }
+
+    #[test]
+    fn test_invalid_compressed_payload() {
+        let signer = Signer::new(
+            b"secretkey",
+            SERIALIZER_SALT,
+            KeyDerivation::default(),
+            Algorithm::SHA1,
+            DEFAULT_SEPARATOR,
+        )
+        .unwrap();
+
+        // authentic but not zlib compressed
+        let token = signer.sign(".eyJhIjoxfQ");
+        let result: Result<serde_json::Value, Error> = UrlSafeSerializer::new(signer).loads(&token);
+        assert_eq!(result, Err(Error::InvalidPayload));
+    }
+
+    #[test]
+    fn test_try_dumps_serialisation_error() {
+        let scores: std::collections::HashMap<(u32, u32), u32> = [((1, 2), 3)].into();
+        let signer = || {
+            Signer::new(
+                b"secretkey",
+                SERIALIZER_SALT,
+                KeyDerivation::DjangoConcat,
+                Algorithm::SHA1,
+                DEFAULT_SEPARATOR,
+            )
+            .unwrap()
+        };
+        assert!(matches!(
+            Serializer::new(signer()).try_dumps(&scores),
+            Err(Error::SerialisationError(_))
+        ));
+        assert!(matches!(
+            UrlSafeSerializer::new(signer()).try_dumps(&scores),
+            Err(Error::SerialisationError(_))
+        ));
+        let serializer = UrlSafeTimedSerializer::flask_session(b"secretkey").unwrap();
+        assert!(matches!(
+            serializer.try_dumps(&scores),
+            Err(Error::SerialisationError(_))
+        ));
+    }
+}
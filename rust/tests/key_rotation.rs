@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use hmac_serialiser::{
+        Algorithm, Encoder, Error, HmacSigner, KeyInfo, MatchedKey, Payload, RotatingSigner,
+    };
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Debug)]
+    struct TestClaim {
+        #[serde(with = "chrono::serde::ts_seconds")]
+        exp: chrono::DateTime<Utc>,
+        data: String,
+    }
+
+    impl Payload for TestClaim {
+        fn get_exp(&self) -> Option<chrono::DateTime<Utc>> {
+            Some(self.exp)
+        }
+    }
+
+    fn key_info(key: &[u8]) -> KeyInfo {
+        KeyInfo {
+            key: key.to_vec(),
+            salt: b"rotation".to_vec(),
+            info: vec![],
+        }
+    }
+
+    fn claim(exp: chrono::DateTime<Utc>) -> TestClaim {
+        TestClaim {
+            exp,
+            data: "test_data".to_string(),
+        }
+    }
+
+    fn setup() -> RotatingSigner {
+        RotatingSigner::new(
+            key_info(b"key_v3"),
+            vec![key_info(b"key_v2"), key_info(b"key_v1")],
+            Algorithm::SHA256,
+            Encoder::UrlSafeNoPadding,
+        )
+    }
+
+    fn sign_with(key: &[u8], claim: &TestClaim) -> String {
+        HmacSigner::new(key_info(key), Algorithm::SHA256, Encoder::UrlSafeNoPadding).sign(claim)
+    }
+
+    #[test]
+    fn test_sign_with_current_key() {
+        let signer = setup();
+        let token = signer.sign(&claim(Utc::now() + Duration::hours(1)));
+
+        let current = HmacSigner::new(
+            key_info(b"key_v3"),
+            Algorithm::SHA256,
+            Encoder::UrlSafeNoPadding,
+        );
+        let verified: TestClaim = current.unsign(&token).unwrap();
+        assert_eq!(verified.data, "test_data");
+
+        let (_, matched_key): (TestClaim, _) = signer.unsign_with_key(&token).unwrap();
+        assert_eq!(matched_key, MatchedKey::Current);
+        assert!(matched_key.is_current());
+    }
+
+    #[test]
+    fn test_unsign_with_fallback_keys() {
+        let signer = setup();
+        for (key, expected) in [
+            (b"key_v2", MatchedKey::Fallback(0)),
+            (b"key_v1", MatchedKey::Fallback(1)),
+        ] {
+            let token = sign_with(key, &claim(Utc::now() + Duration::hours(1)));
+            let (verified, matched_key): (TestClaim, _) = signer.unsign_with_key(&token).unwrap();
+            assert_eq!(verified.data, "test_data");
+            assert_eq!(matched_key, expected);
+            assert!(!matched_key.is_current());
+        }
+    }
+
+    #[test]
+    fn test_unsign_with_unknown_key() {
+        let signer = setup();
+        let token = sign_with(b"key_v0", &claim(Utc::now() + Duration::hours(1)));
+        let result: Result<TestClaim, Error> = signer.unsign(&token);
+        assert_eq!(result.unwrap_err(), Error::InvalidToken);
+    }
+
+    #[test]
+    fn test_unsign_expired_token_with_fallback_key() {
+        let signer = setup();
+        let token = sign_with(b"key_v1", &claim(Utc::now() - Duration::hours(1)));
+        let result: Result<TestClaim, Error> = signer.unsign(&token);
+        assert_eq!(result.unwrap_err(), Error::TokenExpired);
+    }
+}
@@ -0,0 +1,129 @@
+#[cfg(test)]
+mod tests {
+    use hmac_serialiser::{
+        Algorithm, Encoder, EncryptedSigner, Error, HmacSigner, KeyInfo, Keyring, RotatingSigner,
+        TimedSigner,
+    };
+
+    fn key_info(key: &[u8]) -> KeyInfo {
+        KeyInfo {
+            key: key.to_vec(),
+            salt: b"salt".to_vec(),
+            info: vec![],
+        }
+    }
+
+    fn try_new(key: &[u8], min_key_length: usize) -> Result<HmacSigner, Error> {
+        HmacSigner::try_new_with_min_key_length(
+            key_info(key),
+            min_key_length,
+            Algorithm::SHA256,
+            Encoder::UrlSafeNoPadding,
+        )
+    }
+
+    #[test]
+    fn test_try_new_valid_key() {
+        let signer = HmacSigner::try_new(
+            key_info(b"test_secret_key"),
+            Algorithm::SHA256,
+            Encoder::UrlSafeNoPadding,
+        );
+        assert!(signer.is_ok());
+        assert!(try_new(b"k", 1).is_ok());
+        assert!(try_new(b"0123456789abcdef", 16).is_ok());
+    }
+
+    #[test]
+    fn test_try_new_empty_key() {
+        let result = HmacSigner::try_new(key_info(b""), Algorithm::SHA1, Encoder::Standard);
+        assert_eq!(result.unwrap_err(), Error::EmptyKey);
+        assert_eq!(try_new(b"", 0).unwrap_err(), Error::EmptyKey);
+    }
+
+    #[test]
+    fn test_try_new_short_key() {
+        assert_eq!(
+            try_new(b"0123456789abcde", 16).unwrap_err(),
+            Error::KeyTooShort(16)
+        );
+    }
+
+    #[test]
+    fn test_try_new_weak_key() {
+        assert_eq!(try_new(b"aaaaaaaa", 1).unwrap_err(), Error::WeakKey);
+        assert_eq!(try_new(&[0u8; 32], 32).unwrap_err(), Error::WeakKey);
+    }
+
+    #[test]
+    fn test_keyring_rejects_invalid_key() {
+        let result = Keyring::new(
+            "v1",
+            key_info(b""),
+            Algorithm::SHA256,
+            Encoder::UrlSafeNoPadding,
+        );
+        assert_eq!(result.unwrap_err(), Error::EmptyKey);
+    }
+
+    #[test]
+    fn test_timed_and_rotating_signers_reject_invalid_keys() {
+        let result =
+            TimedSigner::try_new(key_info(b""), Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+        assert_eq!(result.unwrap_err(), Error::EmptyKey);
+
+        let result = RotatingSigner::try_new(
+            key_info(b""),
+            vec![key_info(b"old_secret_key")],
+            Algorithm::SHA256,
+            Encoder::UrlSafeNoPadding,
+        );
+        assert_eq!(result.unwrap_err(), Error::EmptyKey);
+
+        let result = RotatingSigner::try_new(
+            key_info(b"new_secret_key"),
+            vec![key_info(b"old_secret_key"), key_info(b"aaaaaaaa")],
+            Algorithm::SHA256,
+            Encoder::UrlSafeNoPadding,
+        );
+        assert_eq!(result.unwrap_err(), Error::WeakKey);
+    }
+
+    #[test]
+    #[should_panic(expected = "key cannot be empty")]
+    fn test_new_panics_on_empty_key() {
+        HmacSigner::new(key_info(b""), Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+    }
+
+    #[test]
+    fn test_key_info_is_zeroized_on_drop() {
+        fn assert_zeroize_on_drop<T: zeroize::ZeroizeOnDrop>() {}
+        assert_zeroize_on_drop::<KeyInfo>();
+    }
+
+    #[test]
+    fn test_debug_redacts_key() {
+        let info = key_info(b"test_secret_key");
+        let debug = format!("{:?}", info);
+        assert!(debug.starts_with("KeyInfo { key_fingerprint: "));
+        assert!(!debug.contains("116, 101, 115, 116"));
+        assert_eq!(debug, format!("{:?}", info.clone()));
+        assert_ne!(debug, format!("{:?}", key_info(b"other_secret_key")));
+
+        // the fingerprint is derived from the key rather than being a plain hash of it
+        let sha256_prefix: String = Algorithm::SHA256.digest(b"test_secret_key")[..4]
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        assert!(!debug.contains(&sha256_prefix));
+
+        let signer = HmacSigner::new(info.clone(), Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+        let debug = format!("{:?}", signer);
+        assert!(debug.starts_with("HmacSigner { key_fingerprint: "));
+        assert!(!debug.contains("expanded_key"));
+
+        let signer = EncryptedSigner::new(info, Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+        let debug = format!("{:?}", signer);
+        assert!(!debug.contains("encryption_key"));
+    }
+}
@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use hmac_serialiser::{Algorithm, Encoder, Error, KeyInfo, Keyring, Payload, DELIM};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Debug)]
+    struct TestClaim {
+        #[serde(with = "chrono::serde::ts_seconds")]
+        exp: chrono::DateTime<Utc>,
+        data: String,
+    }
+
+    impl Payload for TestClaim {
+        fn get_exp(&self) -> Option<chrono::DateTime<Utc>> {
+            Some(self.exp)
+        }
+    }
+
+    fn key_info(key: &[u8]) -> KeyInfo {
+        KeyInfo {
+            key: key.to_vec(),
+            salt: b"keyring".to_vec(),
+            info: vec![],
+        }
+    }
+
+    fn claim() -> TestClaim {
+        TestClaim {
+            exp: Utc::now() + Duration::hours(1),
+            data: "test_data".to_string(),
+        }
+    }
+
+    fn setup() -> Keyring {
+        let mut keyring = Keyring::new(
+            "tenant-a_v1",
+            key_info(b"key_a_v1"),
+            Algorithm::SHA256,
+            Encoder::UrlSafeNoPadding,
+        )
+        .unwrap();
+        keyring
+            .add_key("tenant-b_v1", key_info(b"key_b_v1"))
+            .unwrap();
+        keyring
+    }
+
+    #[test]
+    fn test_sign_and_unsign_with_kid() {
+        let mut keyring = setup();
+        let token = keyring.sign(&claim());
+        assert!(token.starts_with("tenant-a_v1."));
+        assert_eq!(token.split(DELIM).count(), 3);
+
+        keyring.set_current("tenant-b_v1").unwrap();
+        assert_eq!(keyring.current(), "tenant-b_v1");
+        let other_token = keyring.sign(&claim());
+        assert!(other_token.starts_with("tenant-b_v1."));
+
+        for (token, expected_kid) in [(&token, "tenant-a_v1"), (&other_token, "tenant-b_v1")] {
+            let (verified, kid): (TestClaim, _) = keyring.unsign_with_kid(token).unwrap();
+            assert_eq!(verified.data, "test_data");
+            assert_eq!(kid, expected_kid);
+        }
+    }
+
+    #[test]
+    fn test_swapped_kid_is_rejected() {
+        let keyring = setup();
+        let token = keyring.sign(&claim());
+        let swapped_token = token.replacen("tenant-a_v1", "tenant-b_v1", 1);
+        let result: Result<TestClaim, Error> = keyring.unsign(&swapped_token);
+        assert_eq!(result.unwrap_err(), Error::InvalidToken);
+    }
+
+    #[test]
+    fn test_unknown_and_removed_kid() {
+        let mut keyring = setup();
+        keyring.set_current("tenant-b_v1").unwrap();
+        let token = keyring.sign(&claim());
+
+        assert!(!keyring.remove_key("tenant-b_v1"));
+        keyring.set_current("tenant-a_v1").unwrap();
+        assert!(keyring.remove_key("tenant-b_v1"));
+
+        let result: Result<TestClaim, Error> = keyring.unsign(&token);
+        assert_eq!(
+            result.unwrap_err(),
+            Error::UnknownKeyId("tenant-b_v1".to_string())
+        );
+        assert_eq!(
+            keyring.set_current("tenant-b_v1").unwrap_err(),
+            Error::UnknownKeyId("tenant-b_v1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_invalid_kid() {
+        let mut keyring = setup();
+        for kid in ["", "a.b", "tenant a", "ténant"] {
+            assert_eq!(
+                keyring.add_key(kid, key_info(b"key")).unwrap_err(),
+                Error::InvalidKeyId(kid.to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn test_token_without_kid() {
+        let keyring = setup();
+        let token = "payload.signature";
+        let result: Result<TestClaim, Error> = keyring.unsign(token);
+        assert_eq!(result.unwrap_err(), Error::InvalidInput(token.to_string()));
+    }
+}
@@ -0,0 +1,209 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{claim, key_info, key_info_with_key, start, TestClaim};
+    use hmac_serialiser::{
+        Algorithm, Encoder, EncryptedSigner, Error, FixedClock, HmacSigner, Keyring,
+        RotatingSigner, TimedSigner, MAX_LEEWAY,
+    };
+
+    #[test]
+    fn test_leeway() {
+        let signer = HmacSigner::new(key_info(), Algorithm::SHA256, Encoder::UrlSafeNoPadding)
+            .with_leeway(chrono::Duration::seconds(5));
+        let token = signer.sign(&claim(start()));
+
+        let now = start() + chrono::Duration::seconds(5);
+        assert_eq!(
+            signer.unsign_at::<TestClaim>(&token, now).unwrap(),
+            claim(start())
+        );
+
+        let now = now + chrono::Duration::seconds(1);
+        assert_eq!(
+            signer.unsign_at::<TestClaim>(&token, now).unwrap_err(),
+            Error::TokenExpired
+        );
+    }
+
+    #[test]
+    fn test_no_leeway_by_default() {
+        let signer = HmacSigner::new(key_info(), Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+        let token = signer.sign(&claim(start()));
+
+        let now = start() + chrono::Duration::seconds(1);
+        assert_eq!(
+            signer.unsign_at::<TestClaim>(&token, now).unwrap_err(),
+            Error::TokenExpired
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "leeway must not be negative")]
+    fn test_negative_leeway() {
+        let _ = HmacSigner::new(key_info(), Algorithm::SHA256, Encoder::UrlSafeNoPadding)
+            .with_leeway(chrono::Duration::seconds(-5));
+    }
+
+    #[test]
+    #[should_panic(expected = "leeway must not exceed")]
+    fn test_excessive_leeway() {
+        let _ = HmacSigner::new(key_info(), Algorithm::SHA256, Encoder::UrlSafeNoPadding)
+            .with_leeway(MAX_LEEWAY + chrono::Duration::seconds(1));
+    }
+
+    #[test]
+    fn test_leeway_does_not_overflow() {
+        let signer = HmacSigner::new(key_info(), Algorithm::SHA256, Encoder::UrlSafeNoPadding)
+            .with_leeway(chrono::Duration::seconds(5));
+        let claim = TestClaim {
+            username: "user123".to_string(),
+            exp: chrono::DateTime::<chrono::Utc>::MAX_UTC,
+        };
+        let token = signer.sign(&claim);
+        assert_eq!(
+            signer.unsign_at::<TestClaim>(&token, start()).unwrap(),
+            claim
+        );
+
+        let signer = TimedSigner::new(key_info(), Algorithm::SHA256, Encoder::UrlSafeNoPadding)
+            .with_leeway(chrono::Duration::seconds(5));
+        let token = signer.sign(&"user123");
+        assert_eq!(
+            signer
+                .unsign_with_max_age::<String>(&token, chrono::Duration::MAX)
+                .unwrap(),
+            "user123"
+        );
+    }
+
+    #[test]
+    fn test_timed_signer_leeway() {
+        let clock = FixedClock::new(start());
+        let signer = TimedSigner::new(key_info(), Algorithm::SHA256, Encoder::UrlSafeNoPadding)
+            .with_clock(clock.clone())
+            .with_leeway(chrono::Duration::seconds(5));
+        let token = signer.sign(&"user123");
+
+        clock.advance(chrono::Duration::seconds(65));
+        assert!(signer
+            .unsign_with_max_age::<String>(&token, chrono::Duration::minutes(1))
+            .is_ok());
+
+        clock.advance(chrono::Duration::seconds(1));
+        assert_eq!(
+            signer
+                .unsign_with_max_age::<String>(&token, chrono::Duration::minutes(1))
+                .unwrap_err(),
+            Error::TokenExpired
+        );
+    }
+
+    #[test]
+    fn test_encrypted_signer_leeway() {
+        let clock = FixedClock::new(start() + chrono::Duration::seconds(5));
+        let signer = EncryptedSigner::new(key_info(), Algorithm::SHA256, Encoder::UrlSafeNoPadding)
+            .with_clock(clock.clone())
+            .with_leeway(chrono::Duration::seconds(5));
+        let token = signer.sign(&claim(start()));
+        assert!(signer.unsign::<TestClaim>(&token).is_ok());
+
+        clock.advance(chrono::Duration::seconds(1));
+        assert_eq!(
+            signer.unsign::<TestClaim>(&token).unwrap_err(),
+            Error::TokenExpired
+        );
+    }
+
+    #[test]
+    fn test_rotating_signer_leeway() {
+        let old_key = key_info_with_key(b"old_secret_key");
+        let clock = FixedClock::new(start());
+        let old_signer = HmacSigner::new(
+            old_key.clone(),
+            Algorithm::SHA256,
+            Encoder::UrlSafeNoPadding,
+        );
+        let signer = RotatingSigner::new(
+            key_info(),
+            vec![old_key],
+            Algorithm::SHA256,
+            Encoder::UrlSafeNoPadding,
+        )
+        .with_clock(clock.clone())
+        .with_leeway(chrono::Duration::seconds(5));
+
+        for token in [
+            signer.sign(&claim(start())),
+            old_signer.sign(&claim(start())),
+        ] {
+            clock.set(start() + chrono::Duration::seconds(5));
+            assert_eq!(signer.unsign::<TestClaim>(&token).unwrap(), claim(start()));
+
+            clock.advance(chrono::Duration::seconds(1));
+            assert_eq!(
+                signer.unsign::<TestClaim>(&token).unwrap_err(),
+                Error::TokenExpired
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "leeway must not exceed")]
+    fn test_rotating_signer_excessive_leeway() {
+        let _ = RotatingSigner::new(
+            key_info(),
+            vec![],
+            Algorithm::SHA256,
+            Encoder::UrlSafeNoPadding,
+        )
+        .with_leeway(MAX_LEEWAY + chrono::Duration::seconds(1));
+    }
+
+    #[test]
+    fn test_keyring_leeway() {
+        let clock = FixedClock::new(start() + chrono::Duration::seconds(5));
+        let mut keyring = Keyring::new(
+            "v1",
+            key_info(),
+            Algorithm::SHA256,
+            Encoder::UrlSafeNoPadding,
+        )
+        .unwrap()
+        .with_clock(clock.clone())
+        .with_leeway(chrono::Duration::seconds(5));
+        let token = keyring.sign(&claim(start()));
+
+        // keys added after the leeway was set use it as well
+        keyring
+            .add_key("v2", key_info_with_key(b"new_secret_key"))
+            .unwrap();
+        keyring.set_current("v2").unwrap();
+        let new_token = keyring.sign(&claim(start()));
+
+        for token in [token, new_token] {
+            clock.set(start() + chrono::Duration::seconds(5));
+            assert!(keyring.unsign::<TestClaim>(&token).is_ok());
+
+            clock.advance(chrono::Duration::seconds(1));
+            assert_eq!(
+                keyring.unsign::<TestClaim>(&token).unwrap_err(),
+                Error::TokenExpired
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "leeway must not be negative")]
+    fn test_keyring_negative_leeway() {
+        let _ = Keyring::new(
+            "v1",
+            key_info(),
+            Algorithm::SHA256,
+            Encoder::UrlSafeNoPadding,
+        )
+        .unwrap()
+        .with_leeway(chrono::Duration::seconds(-5));
+    }
+}
@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, SubsecRound as _, Utc};
+    use hmac_serialiser::{Algorithm, Encoder, Error, HmacSigner, KeyInfo, Payload, PayloadFormat};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct TestClaim {
+        #[serde(with = "chrono::serde::ts_seconds")]
+        exp: chrono::DateTime<Utc>,
+        user_id: u64,
+        session_id: [u8; 16],
+        roles: Vec<String>,
+    }
+
+    impl Payload for TestClaim {
+        fn get_exp(&self) -> Option<chrono::DateTime<Utc>> {
+            Some(self.exp)
+        }
+    }
+
+    fn setup(format: PayloadFormat) -> HmacSigner {
+        let key_info = KeyInfo {
+            key: b"test_secret_key".to_vec(),
+            salt: b"test_salt".to_vec(),
+            info: vec![],
+        };
+        HmacSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding).with_format(format)
+    }
+
+    fn claim(exp: chrono::DateTime<Utc>) -> TestClaim {
+        TestClaim {
+            // the expiry is serialised in seconds
+            exp: exp.trunc_subsecs(0),
+            user_id: 1234567890,
+            session_id: [0xab; 16],
+            roles: vec!["admin".to_string(), "user".to_string()],
+        }
+    }
+
+    fn assert_round_trip(format: PayloadFormat) {
+        let signer = setup(format.clone());
+        let claim = claim(Utc::now() + Duration::hours(1));
+
+        let token = signer.sign(&claim);
+        let verified_claim: TestClaim = signer.unsign(&token).unwrap();
+        assert_eq!(verified_claim, claim);
+
+        let expired_token = signer.sign(&self::claim(Utc::now() - Duration::hours(1)));
+        let result = signer.unsign::<TestClaim>(&expired_token);
+        assert_eq!(result.unwrap_err(), Error::TokenExpired);
+
+        if format != PayloadFormat::Json {
+            let json_token = setup(PayloadFormat::Json).sign(&claim);
+            assert!(token.len() < json_token.len(), "format: {:?}", format);
+
+            let result = setup(PayloadFormat::Json).unsign::<TestClaim>(&token);
+            assert!(result.is_err(), "format: {:?}", format);
+        }
+    }
+
+    #[test]
+    fn test_json_format() {
+        assert_round_trip(PayloadFormat::Json);
+        assert_eq!(
+            setup(PayloadFormat::default()).sign(&claim(Utc::now())),
+            setup(PayloadFormat::Json).sign(&claim(Utc::now()))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "cbor")]
+    fn test_cbor_format() {
+        assert_round_trip(PayloadFormat::Cbor);
+    }
+
+    #[test]
+    #[cfg(feature = "msgpack")]
+    fn test_msgpack_format() {
+        assert_round_trip(PayloadFormat::MessagePack);
+    }
+
+    #[test]
+    #[cfg(feature = "postcard")]
+    fn test_postcard_format() {
+        assert_round_trip(PayloadFormat::Postcard);
+    }
+
+    #[test]
+    #[cfg(feature = "bincode")]
+    fn test_bincode_format() {
+        assert_round_trip(PayloadFormat::Bincode);
+    }
+}
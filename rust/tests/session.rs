@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod tests {
+    use cookie::CookieJar;
+    use hmac_serialiser::cookie::CookieSigner;
+    use hmac_serialiser::session::{InMemoryRevocationStore, RevocationStore, SessionManager};
+    use hmac_serialiser::{Algorithm, Encoder, Error, FixedClock, HmacSigner, KeyInfo};
+    use std::sync::Arc;
+
+    fn start() -> chrono::DateTime<chrono::Utc> {
+        chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap()
+    }
+
+    fn setup(clock: FixedClock, store: Arc<InMemoryRevocationStore>) -> SessionManager {
+        let key_info = KeyInfo {
+            key: b"test_secret_key".to_vec(),
+            salt: b"salt".to_vec(),
+            info: vec![],
+        };
+        let signer = HmacSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding)
+            .with_clock(clock);
+        SessionManager::new(CookieSigner::new(signer), store)
+    }
+
+    #[test]
+    fn test_login_and_load_session() {
+        let sessions = setup(FixedClock::new(start()), Arc::default());
+        let mut jar = CookieJar::new();
+        sessions.login(&mut jar, "user123", &"dark-theme").unwrap();
+
+        let session = sessions.load::<String>(&jar).unwrap().unwrap();
+        assert_eq!(session.user_id, "user123");
+        assert_eq!(session.generation, 0);
+        assert_eq!(session.issued_at, start());
+        assert_eq!(session.expires_at, start() + chrono::Duration::days(7));
+        assert_eq!(session.data, "dark-theme");
+
+        let cookie = jar.get("session").unwrap();
+        assert_eq!(
+            cookie.max_age(),
+            Some(cookie::time::Duration::seconds(7 * 24 * 60 * 60))
+        );
+    }
+
+    #[test]
+    fn test_logout_everywhere_revokes_all_sessions() {
+        let store = Arc::new(InMemoryRevocationStore::new());
+        let sessions = setup(FixedClock::new(start()), store.clone());
+        let mut laptop = CookieJar::new();
+        let mut phone = CookieJar::new();
+        let mut other_user = CookieJar::new();
+        sessions.login(&mut laptop, "user123", &()).unwrap();
+        sessions.login(&mut phone, "user123", &()).unwrap();
+        sessions.login(&mut other_user, "user456", &()).unwrap();
+
+        sessions.logout_everywhere("user123").unwrap();
+        assert_eq!(store.generation("user123").unwrap(), 1);
+        assert_eq!(store.generation("user456").unwrap(), 0);
+
+        let result = sessions.load::<()>(&laptop).unwrap();
+        assert_eq!(result.unwrap_err(), Error::SessionRevoked);
+        let result = sessions.load::<()>(&phone).unwrap();
+        assert_eq!(result.unwrap_err(), Error::SessionRevoked);
+        assert!(sessions.load::<()>(&other_user).unwrap().is_ok());
+
+        // sessions issued after the revocation carry the new generation
+        sessions.login(&mut laptop, "user123", &()).unwrap();
+        let session = sessions.load::<()>(&laptop).unwrap().unwrap();
+        assert_eq!(session.generation, 1);
+    }
+
+    #[test]
+    fn test_store_shared_between_managers() {
+        let store = Arc::new(InMemoryRevocationStore::new());
+        let first = setup(FixedClock::new(start()), store.clone());
+        let second = setup(FixedClock::new(start()), store);
+
+        let cookie = first.sign("user123", &()).unwrap();
+        second.logout_everywhere("user123").unwrap();
+        assert_eq!(
+            first.unsign::<()>(&cookie).unwrap_err(),
+            Error::SessionRevoked
+        );
+    }
+
+    #[test]
+    fn test_logout_removes_cookie() {
+        let sessions = setup(FixedClock::new(start()), Arc::default());
+        let mut jar = CookieJar::new();
+        sessions.login(&mut jar, "user123", &()).unwrap();
+        sessions.logout(&mut jar);
+        assert!(sessions.load::<()>(&jar).is_none());
+    }
+
+    #[test]
+    fn test_expired_session() {
+        let clock = FixedClock::new(start());
+        let sessions =
+            setup(clock.clone(), Arc::default()).with_lifetime(chrono::Duration::hours(1));
+        let cookie = sessions.sign("user123", &()).unwrap();
+
+        clock.advance(chrono::Duration::hours(2));
+        assert_eq!(
+            sessions.unsign::<()>(&cookie).unwrap_err(),
+            Error::TokenExpired
+        );
+    }
+
+    #[test]
+    fn test_session_issued_by_server_with_clock_ahead() {
+        let issuer = setup(
+            FixedClock::new(start() + chrono::Duration::seconds(30)),
+            Arc::default(),
+        );
+        let cookie = issuer.sign("user123", &()).unwrap();
+
+        let sessions = setup(FixedClock::new(start()), Arc::default());
+        let session = sessions.unsign::<()>(&cookie).unwrap();
+        assert_eq!(session.issued_at, start() + chrono::Duration::seconds(30));
+    }
+
+    #[test]
+    fn test_lifetime_out_of_range() {
+        let sessions =
+            setup(FixedClock::new(start()), Arc::default()).with_lifetime(chrono::Duration::MAX);
+        assert_eq!(
+            sessions.sign("user123", &()).unwrap_err(),
+            Error::InvalidInput("session lifetime is out of range".to_string())
+        );
+    }
+}
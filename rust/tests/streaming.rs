@@ -0,0 +1,121 @@
+#[cfg(test)]
+mod tests {
+    use hmac_serialiser::{Algorithm, Encoder, Error, HmacSigner, KeyInfo, DELIM};
+    use rand::Rng as _;
+    use std::io::{Cursor, Read};
+
+    fn setup(algo: Algorithm) -> HmacSigner {
+        let key_info = KeyInfo {
+            key: b"test_secret_key".to_vec(),
+            salt: b"test_salt".to_vec(),
+            info: b"streaming".to_vec(),
+        };
+        HmacSigner::new(key_info, algo, Encoder::UrlSafeNoPadding)
+    }
+
+    fn get_random_bytes(length: usize) -> Vec<u8> {
+        let mut random_bytes = vec![0u8; length];
+        rand::thread_rng().fill(&mut random_bytes[..]);
+        random_bytes
+    }
+
+    struct FailingReader;
+
+    impl Read for FailingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("disk on fire"))
+        }
+    }
+
+    #[test]
+    fn test_stream_matches_detached_signature() {
+        let payload = get_random_bytes(100_000);
+        for algo in [
+            Algorithm::SHA1,
+            Algorithm::SHA256,
+            Algorithm::SHA384,
+            Algorithm::SHA512,
+        ] {
+            let signer = setup(algo);
+            let signature = signer.sign_detached(&payload);
+
+            let mut stream = signer.stream();
+            for chunk in payload.chunks(999) {
+                stream.update(chunk);
+            }
+            assert_eq!(stream.clone().finalize(), signature);
+            assert_eq!(stream.verify(&signature), Ok(()));
+
+            assert_eq!(
+                signer.sign_reader(Cursor::new(&payload)).unwrap(),
+                signature
+            );
+            assert_eq!(
+                signer.verify_reader(Cursor::new(&payload), &signature),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn test_stream_invalid_signature() {
+        let signer = setup(Algorithm::SHA256);
+        let payload = get_random_bytes(20_000);
+        let signature = signer.sign_reader(Cursor::new(&payload)).unwrap();
+
+        let mut tampered_payload = payload.clone();
+        tampered_payload[10_000] ^= 1;
+        assert_eq!(
+            signer.verify_reader(Cursor::new(&tampered_payload), &signature),
+            Err(Error::InvalidToken)
+        );
+        // truncated signature
+        assert_eq!(
+            signer.verify_reader(Cursor::new(&payload), &signature[..40]),
+            Err(Error::InvalidToken)
+        );
+        assert_eq!(
+            signer.verify_reader(Cursor::new(&payload), "not+valid"),
+            Err(Error::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_stream_reader_error() {
+        let signer = setup(Algorithm::SHA256);
+        assert_eq!(
+            signer.sign_reader(FailingReader),
+            Err(Error::IoError("disk on fire".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_streamed_signature_is_not_a_token_signature() {
+        let signer = setup(Algorithm::SHA256);
+
+        // {"user":"admin"}
+        let encoded_payload = "eyJ1c2VyIjoiYWRtaW4ifQ";
+        let mut stream = signer.stream();
+        stream.update(encoded_payload.as_bytes());
+        let streamed = stream.finalize();
+        let read = signer
+            .sign_reader(Cursor::new(encoded_payload.as_bytes()))
+            .unwrap();
+        assert_eq!(streamed, read);
+
+        for signature in [streamed, read] {
+            let forged_token = format!("{}{}{}", encoded_payload, DELIM, signature);
+            assert_eq!(
+                signer.unsign_str(&forged_token).unwrap_err(),
+                Error::InvalidToken
+            );
+        }
+
+        let token = signer.sign_str(r#"{"user":"admin"}"#);
+        let (encoded_payload, signature) = token.split_once(DELIM).unwrap();
+        assert_eq!(
+            signer.verify_reader(Cursor::new(encoded_payload.as_bytes()), signature),
+            Err(Error::InvalidToken)
+        );
+    }
+}
@@ -0,0 +1,126 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{key_info, start};
+    use hmac_serialiser::{Algorithm, Encoder, EncryptedSigner, Error, HmacSigner, Payload};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Invitation {
+        email: String,
+        nbf: Option<chrono::DateTime<chrono::Utc>>,
+        iat: Option<chrono::DateTime<chrono::Utc>>,
+    }
+
+    impl Payload for Invitation {
+        fn get_exp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+            None
+        }
+
+        fn get_nbf(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+            self.nbf
+        }
+
+        fn get_iat(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+            self.iat
+        }
+    }
+
+    fn invitation(
+        nbf: Option<chrono::DateTime<chrono::Utc>>,
+        iat: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Invitation {
+        Invitation {
+            email: "user123@example.com".to_string(),
+            nbf,
+            iat,
+        }
+    }
+
+    fn setup() -> HmacSigner {
+        HmacSigner::new(key_info(), Algorithm::SHA256, Encoder::UrlSafeNoPadding)
+    }
+
+    #[test]
+    fn test_not_before() {
+        let signer = setup();
+        let token = signer.sign(&invitation(Some(start()), None));
+
+        assert_eq!(
+            signer
+                .unsign_at::<Invitation>(&token, start() - chrono::Duration::seconds(1))
+                .unwrap_err(),
+            Error::TokenNotYetValid
+        );
+        assert_eq!(
+            signer.unsign_at::<Invitation>(&token, start()).unwrap(),
+            invitation(Some(start()), None)
+        );
+    }
+
+    #[test]
+    fn test_issued_at() {
+        let signer = setup();
+        let token = signer.sign(&invitation(None, Some(start())));
+
+        assert_eq!(
+            signer
+                .unsign_at::<Invitation>(&token, start() - chrono::Duration::seconds(1))
+                .unwrap_err(),
+            Error::TokenIssuedInFuture
+        );
+        assert!(signer.unsign_at::<Invitation>(&token, start()).is_ok());
+        assert!(signer.unsign::<Invitation>(&token).is_ok());
+    }
+
+    #[test]
+    fn test_time_claims_leeway() {
+        let signer = setup().with_leeway(chrono::Duration::seconds(5));
+        let token = signer.sign(&invitation(Some(start()), Some(start())));
+
+        let now = start() - chrono::Duration::seconds(5);
+        assert!(signer.unsign_at::<Invitation>(&token, now).is_ok());
+
+        let now = now - chrono::Duration::seconds(1);
+        assert_eq!(
+            signer.unsign_at::<Invitation>(&token, now).unwrap_err(),
+            Error::TokenNotYetValid
+        );
+    }
+
+    #[test]
+    fn test_time_claims_not_checked_by_default() {
+        #[derive(Serialize, Deserialize, Debug)]
+        struct UserData {
+            username: String,
+        }
+
+        impl Payload for UserData {
+            fn get_exp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+                None
+            }
+        }
+
+        let user = UserData {
+            username: "user123".to_string(),
+        };
+        assert!(user.get_nbf().is_none());
+        assert!(user.get_iat().is_none());
+
+        let signer = setup();
+        let token = signer.sign(&user);
+        assert!(signer.unsign_at::<UserData>(&token, start()).is_ok());
+    }
+
+    #[test]
+    fn test_encrypted_signer_not_before() {
+        let signer = EncryptedSigner::new(key_info(), Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+        let nbf = chrono::Utc::now() + chrono::Duration::days(1);
+        let token = signer.sign(&invitation(Some(nbf), None));
+        assert_eq!(
+            signer.unsign::<Invitation>(&token).unwrap_err(),
+            Error::TokenNotYetValid
+        );
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use hmac_serialiser::{Algorithm, Encoder, Error, KeyInfo, TimedSigner, DELIM};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct InviteData {
+        team: String,
+        email: String,
+    }
+
+    fn setup(algo: Algorithm, encoder: Encoder) -> TimedSigner {
+        let key_info = KeyInfo {
+            key: b"test_secret_key".to_vec(),
+            salt: b"invite".to_vec(),
+            info: vec![],
+        };
+        TimedSigner::new(key_info, algo, encoder)
+    }
+
+    fn invite() -> InviteData {
+        InviteData {
+            team: "core".to_string(),
+            email: "user123@example.com".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_sign_and_unsign_within_max_age() {
+        let signer = setup(Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+        let token = signer.sign(&invite());
+        println!("Token: {}", token);
+        assert_eq!(token.split(DELIM).count(), 3);
+
+        let verified: InviteData = signer
+            .unsign_with_max_age(&token, Duration::minutes(5))
+            .unwrap();
+        assert_eq!(verified, invite());
+    }
+
+    #[test]
+    fn test_unsign_expired_token() {
+        let signer = setup(Algorithm::SHA512, Encoder::Standard);
+        let token = signer.sign_at(&invite(), Utc::now() - Duration::hours(1));
+
+        let result: Result<InviteData, Error> =
+            signer.unsign_with_max_age(&token, Duration::minutes(30));
+        assert!(matches!(result, Err(Error::TokenExpired)));
+
+        // without a max age, the token is still authentic
+        let verified: InviteData = signer.unsign(&token).unwrap();
+        assert_eq!(verified, invite());
+    }
+
+    #[test]
+    fn test_unsign_with_timestamp() {
+        let signer = setup(Algorithm::SHA1, Encoder::UrlSafe);
+        let signed_at = chrono::DateTime::from_timestamp(1706745600, 0).unwrap();
+        let token = signer.sign_at(&invite(), signed_at);
+
+        let (verified, timestamp): (InviteData, _) = signer.unsign_with_timestamp(&token).unwrap();
+        assert_eq!(verified, invite());
+        assert_eq!(timestamp, signed_at);
+    }
+
+    #[test]
+    fn test_tampered_timestamp() {
+        let signer = setup(Algorithm::SHA384, Encoder::UrlSafeNoPadding);
+        let token = signer.sign_at(&invite(), Utc::now() - Duration::hours(1));
+        let newer_token = signer.sign(&invite());
+
+        let parts: Vec<&str> = token.split(DELIM).collect();
+        let newer_timestamp = newer_token.split(DELIM).collect::<Vec<&str>>()[1];
+        let tampered_token = format!("{}.{}.{}", parts[0], newer_timestamp, parts[2]);
+
+        let result: Result<InviteData, Error> =
+            signer.unsign_with_max_age(&tampered_token, Duration::minutes(30));
+        assert!(matches!(result, Err(Error::InvalidToken)));
+    }
+
+    #[test]
+    fn test_untimed_token_is_rejected() {
+        let signer = setup(Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+        let token = "payload.signature";
+        let result: Result<InviteData, Error> = signer.unsign(token);
+        assert_eq!(result.unwrap_err(), Error::InvalidInput(token.to_string()));
+    }
+
+    #[test]
+    fn test_try_sign_serialisation_error() {
+        let signer = setup(Algorithm::SHA256, Encoder::UrlSafeNoPadding);
+        let scores: std::collections::HashMap<(u32, u32), u32> = [((1, 2), 3)].into();
+        assert!(matches!(
+            signer.try_sign(&scores),
+            Err(Error::SerialisationError(_))
+        ));
+        assert!(signer.try_sign(&invite()).is_ok());
+    }
+}
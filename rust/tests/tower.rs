@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod tests {
+    use hmac_serialiser::tower::TokenLayer;
+    use hmac_serialiser::web::{TokenLocation, TokenVerifier};
+    use hmac_serialiser::{Algorithm, Encoder, Error, HmacSigner, KeyInfo, Payload};
+    use http::{header, Request, Response, StatusCode};
+    use serde::{Deserialize, Serialize};
+    use std::convert::Infallible;
+    use tower::{ServiceBuilder, ServiceExt as _};
+
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    struct TestClaim {
+        username: String,
+        exp: chrono::DateTime<chrono::Utc>,
+    }
+
+    impl Payload for TestClaim {
+        fn get_exp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+            Some(self.exp)
+        }
+    }
+
+    fn setup() -> HmacSigner {
+        let key_info = KeyInfo {
+            key: b"test_secret_key".to_vec(),
+            salt: b"salt".to_vec(),
+            info: vec![],
+        };
+        HmacSigner::new(key_info, Algorithm::SHA256, Encoder::UrlSafeNoPadding)
+    }
+
+    fn token(signer: &HmacSigner, exp: chrono::Duration) -> String {
+        signer.sign(&TestClaim {
+            username: "user123".to_string(),
+            exp: chrono::Utc::now() + exp,
+        })
+    }
+
+    async fn username(request: Request<String>) -> Result<Response<String>, Infallible> {
+        let claim = request.extensions().get::<TestClaim>().unwrap();
+        Ok(Response::new(claim.username.clone()))
+    }
+
+    #[tokio::test]
+    async fn test_layer() {
+        let signer = setup();
+        let service = ServiceBuilder::new()
+            .layer(TokenLayer::<TestClaim>::new(TokenVerifier::new(
+                signer.clone(),
+            )))
+            .service_fn(username);
+
+        let request = Request::get("/")
+            .header(
+                header::AUTHORIZATION,
+                format!("Bearer {}", token(&signer, chrono::Duration::minutes(5))),
+            )
+            .body(String::new())
+            .unwrap();
+        let response = service.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.body(), "user123");
+
+        let request = Request::get("/")
+            .header(
+                header::AUTHORIZATION,
+                format!("Bearer {}", token(&signer, chrono::Duration::minutes(-5))),
+            )
+            .body(String::new())
+            .unwrap();
+        let response = service.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+        assert!(response.body().contains("\"token_expired\""));
+
+        let request = Request::get("/").body(String::new()).unwrap();
+        let response = service.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert!(response.body().contains("\"missing_token\""));
+    }
+
+    #[tokio::test]
+    async fn test_layer_custom_rejection() {
+        let signer = setup();
+        let verifier = TokenVerifier::new(signer.clone())
+            .with_location(TokenLocation::Header("x-api-token".to_string()));
+        let layer = TokenLayer::<TestClaim>::new(verifier).with_rejection(|error: Error| {
+            let mut response = Response::new(error.error_code().to_string());
+            *response.status_mut() = StatusCode::FORBIDDEN;
+            response
+        });
+        let service = ServiceBuilder::new().layer(layer).service_fn(username);
+
+        let request = Request::get("/")
+            .header("x-api-token", token(&signer, chrono::Duration::minutes(5)))
+            .body(String::new())
+            .unwrap();
+        let response = service.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.body(), "user123");
+
+        let request = Request::get("/")
+            .header("x-api-token", "invalid.token")
+            .body(String::new())
+            .unwrap();
+        let response = service.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(response.body(), "invalid_signature");
+    }
+
+    #[cfg(feature = "axum")]
+    #[tokio::test]
+    async fn test_layer_with_axum() {
+        use axum::{body::Body, routing::get, Extension, Router};
+
+        let signer = setup();
+        let app = Router::new()
+            .route(
+                "/",
+                get(|Extension(claim): Extension<TestClaim>| async move { claim.username }),
+            )
+            .layer(TokenLayer::<TestClaim>::new(TokenVerifier::new(
+                signer.clone(),
+            )));
+
+        let request = Request::get("/")
+            .header(
+                header::AUTHORIZATION,
+                format!("Bearer {}", token(&signer, chrono::Duration::minutes(5))),
+            )
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let request = Request::get("/").body(Body::empty()).unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+}
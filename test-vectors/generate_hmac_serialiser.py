@@ -0,0 +1,137 @@
+import json
+import hmac
+import base64
+import hashlib
+import typing
+
+# Reproduces the wire format of the C# HMACSerialiser library so that
+# both the C# and the Rust test suites can consume the same test vectors.
+
+FILE_PATH = "./test-vectors/hmac_serialiser.json"
+DEFAULT_SALT = b"default.salt"
+TIMESTAMP = 1706745600 # 2024-02-01T00:00:00Z
+MAX_AGE = 3153600000 # 100 years, so that the timed vectors never expire
+
+HASH_FNS = {
+    "SHA1": (hashlib.sha1, 64),
+    "SHA256": (hashlib.sha256, 64),
+    "SHA384": (hashlib.sha384, 128),
+    "SHA512": (hashlib.sha512, 128),
+}
+
+SHORT_ESCAPES = {"\\": "\\\\", "\b": "\\b", "\f": "\\f", "\n": "\\n", "\r": "\\r", "\t": "\\t"}
+HTML_SENSITIVE_CHARS = "\"&'+<>`"
+
+SERIALISERS = ["serialiser", "urlsafe-serialiser", "timed-serialiser", "timed-urlsafe-serialiser"]
+
+def hkdf(hash_fn: typing.Any, ikm: bytes, salt: bytes, info: bytes, length: int) -> bytes:
+    if not salt:
+        salt = b"\x00" * hash_fn().digest_size
+    prk = hmac.new(salt, ikm, hash_fn).digest()
+
+    okm, t, i = b"", b"", 1
+    while len(okm) < length:
+        t = hmac.new(prk, t + info + bytes([i]), hash_fn).digest()
+        okm += t
+        i += 1
+    return okm[:length]
+
+def b64encode(data: bytes, urlsafe: bool) -> str:
+    encoded = base64.urlsafe_b64encode(data) if urlsafe else base64.b64encode(data)
+    return encoded.decode("utf-8").replace("=", "")
+
+def escape_json_string(value: str) -> str:
+    # System.Text.Json's default JavaScriptEncoder only leaves printable ASCII
+    # characters unescaped, except for the HTML-sensitive ones
+    escaped = ""
+    for c in value:
+        if c in SHORT_ESCAPES:
+            escaped += SHORT_ESCAPES[c]
+        elif " " <= c < "\x7f" and c not in HTML_SENSITIVE_CHARS:
+            escaped += c
+        else:
+            utf16 = c.encode("utf-16-be")
+            for i in range(0, len(utf16), 2):
+                escaped += f"\\u{int.from_bytes(utf16[i:i + 2], 'big'):04X}"
+    return f'"{escaped}"'
+
+def dump_json(payload: typing.Any) -> str:
+    # compact JSON with sorted keys, escaped like C#'s JsonSerializer
+    if isinstance(payload, dict):
+        items = sorted(payload.items())
+        return "{" + ",".join(f"{escape_json_string(k)}:{dump_json(v)}" for k, v in items) + "}"
+    if isinstance(payload, list):
+        return "[" + ",".join(dump_json(v) for v in payload) + "]"
+    if isinstance(payload, str):
+        return escape_json_string(payload)
+    if isinstance(payload, bool) or payload is None or isinstance(payload, int):
+        return json.dumps(payload)
+    raise TypeError(f"unsupported payload type: {type(payload)}")
+
+def serialise_object(payload: typing.Any) -> bytes:
+    # C#'s SerialiseObject() signs strings as is and everything else as compact JSON
+    if isinstance(payload, str):
+        return payload.encode("utf-8")
+    return dump_json(payload).encode("utf-8")
+
+def dumps(vector: dict[str, typing.Any]) -> str:
+    hash_fn, length = HASH_FNS[vector["algorithm"]]
+    salt = DEFAULT_SALT if vector["salt"] is None else vector["salt"].encode("utf-8")
+    info = b"" if vector["info"] is None else vector["info"].encode("utf-8")
+    key = hkdf(hash_fn, vector["key"].encode("utf-8"), salt, info, length)
+
+    serialiser = vector["serialiser"]
+    urlsafe = "urlsafe" in serialiser
+    sep = vector["sep"]
+    data = b64encode(serialise_object(vector["payload"]), urlsafe)
+    if serialiser.startswith("timed"):
+        data += sep + b64encode(str(vector["timestamp"]).encode("utf-8"), urlsafe)
+
+    signature = hmac.new(key, data.encode("utf-8"), hash_fn).digest()
+    return data + sep + b64encode(signature, urlsafe)
+
+def vector(name: str, serialiser: str, algorithm: str, **kwargs: typing.Any) -> dict[str, typing.Any]:
+    vector = {
+        "name": name,
+        "serialiser": serialiser,
+        "algorithm": algorithm,
+        "key": kwargs.get("key", "secretkey"),
+        "salt": kwargs.get("salt", "randomsalt"),
+        "info": kwargs.get("info", "testing"),
+        "sep": kwargs.get("sep", "."),
+        "payload": kwargs.get("payload", "KJHJason/HMACSerialiser"),
+    }
+    if serialiser.startswith("timed"):
+        vector["timestamp"] = TIMESTAMP
+        vector["max_age"] = MAX_AGE
+    vector["token"] = dumps(vector)
+    return vector
+
+def main() -> None:
+    vectors = []
+    for algorithm in HASH_FNS:
+        for serialiser in SERIALISERS:
+            vectors.append(vector(f"{algorithm.lower()}-{serialiser}", serialiser, algorithm))
+
+    json_payload = {"exp": TIMESTAMP, "roles": ["admin", "user"], "username": "user123"}
+    escaped_payload = {
+        "html": "<a href='/?a=1&b=2+3'>`hi`</a>",
+        "quote": "say \"hi\"\\\n\t\x01\x7f",
+        "unicode": "héllo wörld ✓ 🦀",
+    }
+    for serialiser in SERIALISERS:
+        vectors.append(vector(f"default-salt-{serialiser}", serialiser, "SHA1", salt=None, info=None))
+        vectors.append(vector(f"json-payload-{serialiser}", serialiser, "SHA256", payload=json_payload))
+        vectors.append(vector(f"utf8-payload-{serialiser}", serialiser, "SHA384", payload="héllo wörld ✓"))
+        vectors.append(vector(f"custom-separator-{serialiser}", serialiser, "SHA512", sep="~"))
+        vectors.append(vector(f"multi-char-separator-{serialiser}", serialiser, "SHA256", sep="::"))
+        vectors.append(vector(f"escaped-json-payload-{serialiser}", serialiser, "SHA256", payload=escaped_payload))
+
+    with open(FILE_PATH, "w", encoding="utf-8") as file:
+        json.dump({"vectors": vectors}, file, indent=2, ensure_ascii=False)
+        file.write("\n")
+
+    print(f"Dumped {len(vectors)} test vectors to {FILE_PATH}")
+
+if __name__ == "__main__":
+    main()
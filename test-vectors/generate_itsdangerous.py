@@ -0,0 +1,78 @@
+import json
+import hashlib
+import typing
+
+import flask
+import itsdangerous
+
+# Generates tokens using Python's ItsDangerous library (and Flask for the session cookies)
+# so that the Rust crate's itsdangerous compatibility module can be verified against them.
+
+FILE_PATH = "./test-vectors/itsdangerous.json"
+TIMESTAMP = 1706745600 # 2024-02-01T00:00:00Z
+SECRET_KEY = "secretkey"
+
+HASH_FNS = {
+    "SHA1": hashlib.sha1,
+    "SHA256": hashlib.sha256,
+    "SHA384": hashlib.sha384,
+    "SHA512": hashlib.sha512,
+}
+
+PAYLOADS: list[typing.Any] = [
+    "KJHJason/HMACSerialiser",
+    # keys are sorted so that the Rust tests can re-serialise the payloads as is
+    {"id": 123, "roles": ["admin", "user"], "username": "user123"},
+    {"name": "héllo wörld ✓"},
+    # ensure_ascii escapes DEL even though it is an ASCII character
+    {"name": "tab\tdel\x7f"},
+    # long and repetitive so that the URL-safe serialisers compress it
+    {"permissions": ["read:documents"] * 32},
+]
+
+def main() -> None:
+    # freeze the timestamp used by the timestamp signers
+    itsdangerous.TimestampSigner.get_timestamp = lambda self: TIMESTAMP # type: ignore
+
+    vectors: list[dict[str, typing.Any]] = []
+    for algorithm, hash_fn in HASH_FNS.items():
+        for key_derivation in ["concat", "django-concat", "hmac", "none"]:
+            kwargs = {"key_derivation": key_derivation, "digest_method": hash_fn}
+            signer = itsdangerous.Signer(SECRET_KEY, salt="signer-salt", **kwargs)
+            timestamp_signer = itsdangerous.TimestampSigner(SECRET_KEY, salt="signer-salt", sep="~", **kwargs)
+            common = {"algorithm": algorithm, "key_derivation": key_derivation, "secret_key": SECRET_KEY}
+            vectors.append({**common, "kind": "signer", "salt": "signer-salt", "sep": ".",
+                            "payload": "value", "timestamp": None, "token": signer.sign("value").decode()})
+            vectors.append({**common, "kind": "timestamp-signer", "salt": "signer-salt", "sep": "~",
+                            "payload": "value", "timestamp": TIMESTAMP, "token": timestamp_signer.sign("value").decode()})
+
+        signer_kwargs = {"digest_method": hash_fn}
+        for payload in PAYLOADS:
+            common = {"algorithm": algorithm, "key_derivation": "django-concat", "secret_key": SECRET_KEY,
+                      "salt": "itsdangerous", "sep": ".", "payload": payload}
+            serializers = {
+                "serializer": itsdangerous.Serializer(SECRET_KEY, signer_kwargs=signer_kwargs),
+                "url-safe-serializer": itsdangerous.URLSafeSerializer(SECRET_KEY, signer_kwargs=signer_kwargs),
+                "url-safe-timed-serializer": itsdangerous.URLSafeTimedSerializer(SECRET_KEY, signer_kwargs=signer_kwargs),
+            }
+            for kind, serializer in serializers.items():
+                timestamp = TIMESTAMP if kind == "url-safe-timed-serializer" else None
+                vectors.append({**common, "kind": kind, "timestamp": timestamp, "token": serializer.dumps(payload)})
+
+    # Flask's default session cookies
+    app = flask.Flask(__name__)
+    app.secret_key = SECRET_KEY
+    session_serializer = app.session_interface.get_signing_serializer(app) # type: ignore
+    for payload in PAYLOADS[1:]:
+        vectors.append({"kind": "flask-session", "algorithm": "SHA1", "key_derivation": "hmac",
+                        "secret_key": SECRET_KEY, "salt": "cookie-session", "sep": ".", "payload": payload,
+                        "timestamp": TIMESTAMP, "token": session_serializer.dumps(payload)})
+
+    with open(FILE_PATH, "w", encoding="utf-8") as file:
+        json.dump({"vectors": vectors}, file, indent=2, ensure_ascii=False)
+        file.write("\n")
+
+    print(f"Dumped {len(vectors)} test vectors to {FILE_PATH}")
+
+if __name__ == "__main__":
+    main()